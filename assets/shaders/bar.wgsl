@@ -3,10 +3,11 @@
 #import bevy_pbr::mesh_functions
 
 let BACKGROUND_COLOR = vec4<f32>(0., 0., 0., 0.75);
-let FOREGROUND_COLOR = vec4<f32>(0.6, 1., 0.6, 0.75);
 
 @group(1) @binding(0)
 var<uniform> value: f32;
+@group(1) @binding(1)
+var<uniform> color: vec4<f32>;
 
 struct Vertex {
     @location(0) position: vec3<f32>,
@@ -37,9 +38,9 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 
 @fragment
 fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
-    var color = FOREGROUND_COLOR;
+    var out_color = color;
     if in.x > value {
-        color = BACKGROUND_COLOR;
+        out_color = BACKGROUND_COLOR;
     }
-    return color;
+    return out_color;
 }
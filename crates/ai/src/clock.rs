@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use de_core::{stages::GameStage, state::GameState};
+use iyes_loopless::prelude::*;
+
+use crate::{
+    players::{AiPlayers, Difficulty},
+    AiLabels,
+};
+
+/// Duration of a single AI clock tick.
+const TICK: Duration = Duration::from_secs(1);
+
+pub(crate) struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AiPlayers>()
+            .add_enter_system(GameState::Playing, setup)
+            .add_exit_system(GameState::Playing, cleanup)
+            .add_system_to_stage(
+                GameStage::PreUpdate,
+                tick.run_in_state(GameState::Playing).label(AiLabels::Clock),
+            );
+    }
+}
+
+/// Clock which paces decisions of computer opponents.
+pub(crate) struct DecisionClock {
+    timer: Timer,
+    ticks: u32,
+}
+
+impl DecisionClock {
+    /// Returns true if a computer opponent of a given difficulty should make
+    /// a decision during the current frame.
+    pub(crate) fn decides(&self, difficulty: Difficulty) -> bool {
+        self.timer.just_finished() && self.ticks % difficulty.decision_period() == 0
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        if self.timer.tick(delta).just_finished() {
+            self.ticks = self.ticks.wrapping_add(1);
+        }
+    }
+}
+
+impl Default for DecisionClock {
+    fn default() -> Self {
+        Self {
+            timer: Timer::new(TICK, true),
+            ticks: 0,
+        }
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.insert_resource(DecisionClock::default());
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<DecisionClock>();
+}
+
+fn tick(time: Res<Time>, mut clock: ResMut<DecisionClock>) {
+    clock.tick(time.delta());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock() {
+        let mut clock = DecisionClock::default();
+        clock.tick(Duration::from_millis(500));
+        assert!(!clock.decides(Difficulty::Hard));
+
+        clock.tick(Duration::from_millis(500));
+        assert!(clock.decides(Difficulty::Hard));
+        assert!(!clock.decides(Difficulty::Normal));
+
+        clock.tick(Duration::from_millis(1000));
+        assert!(clock.decides(Difficulty::Hard));
+        assert!(clock.decides(Difficulty::Normal));
+        assert!(!clock.decides(Difficulty::Easy));
+
+        clock.tick(Duration::from_millis(100));
+        assert!(!clock.decides(Difficulty::Hard));
+    }
+}
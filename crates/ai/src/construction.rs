@@ -0,0 +1,195 @@
+use std::f32::consts::PI;
+
+use ahash::AHashMap;
+use bevy::prelude::*;
+use de_core::{
+    objects::{ActiveObjectType, BuildingType, ObjectType, StaticSolid},
+    player::Player,
+    projection::{ToFlat, ToMsl},
+    stages::GameStage,
+    state::GameState,
+};
+use de_economy::Stockpiles;
+use de_objects::ObjectCache;
+use de_spawner::{Draft, DraftBundle, SpawnBundle};
+use iyes_loopless::prelude::*;
+
+use crate::{clock::DecisionClock, players::AiPlayers, AiLabels};
+
+/// Golden angle (in radians). Consecutive Power Hubs are placed around the
+/// base at this angle from each other so that they are evenly spread.
+const GOLDEN_ANGLE: f32 = PI * (3. - 2.236_068);
+/// Distance (in meters) of the first Power Hub from the base.
+const MIN_DISTANCE: f32 = 30.;
+/// Each following Power Hub is further from the base by this distance (in
+/// meters).
+const DISTANCE_STEP: f32 = 6.;
+/// Each Turret is placed this far (in meters) behind the Power Hub placed
+/// right before it so that the Turret is well within the power range of the
+/// hub.
+const TURRET_OFFSET: f32 = 6.;
+/// Maximum number of consecutive placement attempts. The sequence of
+/// building positions starts over afterwards.
+const MAX_ATTEMPTS: u32 = 64;
+
+pub(crate) struct ConstructionPlugin;
+
+impl Plugin for ConstructionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set_to_stage(
+            GameStage::PreUpdate,
+            SystemSet::new()
+                .with_system(
+                    construct
+                        .run_in_state(GameState::Playing)
+                        .label(ConstructionLabels::Construct),
+                )
+                .with_system(
+                    plan.run_in_state(GameState::Playing)
+                        .after(AiLabels::Clock)
+                        .after(ConstructionLabels::Construct),
+                ),
+        );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemLabel)]
+enum ConstructionLabels {
+    Construct,
+}
+
+/// Returns type and relative (to the base) position of n-th building
+/// placement attempt.
+///
+/// Power Hubs and Turrets alternate. Turrets consume energy, thus each Turret
+/// is placed next to the Power Hub placed right before it.
+fn placement(attempt: u32) -> (BuildingType, Vec2) {
+    let slot = attempt / 2;
+    let angle = slot as f32 * GOLDEN_ANGLE;
+    let direction = Vec2::new(angle.cos(), angle.sin());
+    let distance = MIN_DISTANCE + slot as f32 * DISTANCE_STEP;
+    if attempt % 2 == 0 {
+        (BuildingType::PowerHub, distance * direction)
+    } else {
+        (BuildingType::Turret, (distance + TURRET_OFFSET) * direction)
+    }
+}
+
+/// Places a (hidden) construction draft of a new building near the base of
+/// each computer player which decides during the current frame.
+fn plan(
+    mut commands: Commands,
+    mut attempts: Local<AHashMap<Player, u32>>,
+    ai_players: Res<AiPlayers>,
+    clock: Res<DecisionClock>,
+    stockpiles: Res<Stockpiles>,
+    cache: Res<ObjectCache>,
+    buildings: Query<(&Player, &ObjectType, &Transform), With<StaticSolid>>,
+) {
+    for (player, difficulty) in ai_players.iter() {
+        if !clock.decides(difficulty) {
+            continue;
+        }
+
+        let mut count = 0;
+        let mut base = None;
+        for (&owner, &object_type, transform) in buildings.iter() {
+            if owner != player {
+                continue;
+            }
+            count += 1;
+            if matches!(
+                object_type,
+                ObjectType::Active(ActiveObjectType::Building(BuildingType::Base))
+            ) {
+                base = Some(transform.translation.to_flat());
+            }
+        }
+
+        let base = match base {
+            Some(base) => base,
+            None => continue,
+        };
+        if count >= difficulty.max_buildings() {
+            continue;
+        }
+
+        let attempt = attempts.entry(player).or_default();
+        let (building_type, offset) = placement(*attempt);
+        let object_type = ObjectType::Active(ActiveObjectType::Building(building_type));
+        if !stockpiles.can_afford(player, cache.get(object_type).cost()) {
+            continue;
+        }
+        *attempt = (*attempt + 1) % MAX_ATTEMPTS;
+
+        commands
+            .spawn_bundle(DraftBundle::new(
+                building_type,
+                Transform::from_translation((base + offset).to_msl()),
+            ))
+            .insert(Visibility { is_visible: false })
+            .insert(player);
+    }
+}
+
+/// Turns construction drafts of computer players into buildings. Drafts at
+/// disallowed positions and drafts which the player cannot afford are
+/// discarded.
+fn construct(
+    mut commands: Commands,
+    ai_players: Res<AiPlayers>,
+    mut stockpiles: ResMut<Stockpiles>,
+    cache: Res<ObjectCache>,
+    drafts: Query<(Entity, &Transform, &ObjectType, &Draft, &Player)>,
+) {
+    for (entity, &transform, &object_type, draft, &player) in drafts.iter() {
+        if ai_players.get(player).is_none() {
+            continue;
+        }
+
+        commands.entity(entity).despawn_recursive();
+        if draft.allowed() && stockpiles.spend(player, cache.get(object_type).cost()) {
+            commands
+                .spawn_bundle(SpawnBundle::construction(object_type, transform))
+                .insert(player);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placement() {
+        let (building_type, offset) = placement(0);
+        assert_eq!(building_type, BuildingType::PowerHub);
+        assert!(offset.distance(Vec2::new(30., 0.)) < 0.001);
+
+        let (building_type, offset) = placement(1);
+        assert_eq!(building_type, BuildingType::Turret);
+        assert!(offset.distance(Vec2::new(36., 0.)) < 0.001);
+
+        let (building_type, offset) = placement(2);
+        assert_eq!(building_type, BuildingType::PowerHub);
+        assert!((offset.length() - 36.).abs() < 0.001);
+
+        let first = placement(3).1.normalize();
+        let second = placement(5).1.normalize();
+        assert!((first.angle_between(second) - GOLDEN_ANGLE).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_turrets_powered() {
+        // Power range of a Power Hub, see assets/objects/powerhub.obj.json.
+        const POWER_HUB_RANGE: f32 = 40.;
+
+        for attempt in (1..MAX_ATTEMPTS).step_by(2) {
+            let (hub_type, hub) = placement(attempt - 1);
+            assert_eq!(hub_type, BuildingType::PowerHub);
+            let (turret_type, turret) = placement(attempt);
+            assert_eq!(turret_type, BuildingType::Turret);
+            assert!(hub.distance(turret) <= POWER_HUB_RANGE);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use ahash::AHashSet;
+use bevy::prelude::*;
+use de_behaviour::{ChaseTarget, Order, OrderEvent, OrderMode, Retreat};
+use de_combat::{FocusEvent, HitEvent};
+use de_core::{
+    objects::{MovableSolid, StaticSolid},
+    player::Player,
+    projection::ToFlat,
+    stages::GameStage,
+    state::GameState,
+};
+use de_objects::Armament;
+use iyes_loopless::prelude::*;
+
+use crate::players::AiPlayers;
+
+pub(crate) struct DefensePlugin;
+
+impl Plugin for DefensePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            GameStage::PreUpdate,
+            defend.run_in_state(GameState::Playing),
+        );
+    }
+}
+
+/// Armed units which are neither attacking nor retreating.
+type Defenders<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Player, &'static Transform),
+    (
+        With<MovableSolid>,
+        With<Armament>,
+        Without<ChaseTarget>,
+        Without<Retreat>,
+    ),
+>;
+
+/// Sends nearby armed units of a computer player against enemies which
+/// damage its buildings. Each player responds to at most one attack per
+/// frame.
+fn defend(
+    ai_players: Res<AiPlayers>,
+    mut hits: EventReader<HitEvent>,
+    mut orders: EventWriter<OrderEvent>,
+    mut focus: EventWriter<FocusEvent>,
+    buildings: Query<(&Player, &Transform), With<StaticSolid>>,
+    attackers: Query<&Player>,
+    defenders: Defenders,
+) {
+    let mut responded = AHashSet::new();
+
+    for event in hits.iter() {
+        let (player, position) = match buildings.get(event.target()) {
+            Ok((&player, transform)) => (player, transform.translation.to_flat()),
+            Err(_) => continue,
+        };
+        let difficulty = match ai_players.get(player) {
+            Some(difficulty) => difficulty,
+            None => continue,
+        };
+        let attacker = match event.attacker() {
+            Some(attacker) => attacker,
+            None => continue,
+        };
+        match attackers.get(attacker) {
+            Ok(&owner) if owner != player => (),
+            _ => continue,
+        }
+        if !responded.insert(player) {
+            continue;
+        }
+
+        let radius_squared = difficulty.defense_radius().powi(2);
+        for (entity, &owner, transform) in defenders.iter() {
+            if owner == player
+                && transform.translation.to_flat().distance_squared(position) <= radius_squared
+            {
+                orders.send(OrderEvent::new(
+                    entity,
+                    Order::Attack(attacker),
+                    OrderMode::Replace,
+                ));
+            }
+        }
+        focus.send(FocusEvent::new(player, attacker));
+    }
+}
@@ -0,0 +1,45 @@
+//! This crate implements computer opponents (skirmish AI).
+//!
+//! Each player listed in [`AiPlayers`] is controlled by the computer. The AI
+//! issues the same commands as a human player: it places buildings via
+//! construction drafts, produces units with
+//! [`de_production::ProductionEvent`], gives orders to its units with
+//! [`de_behaviour::OrderEvent`] and focuses enemies with
+//! [`de_combat::FocusEvent`].
+//!
+//! The AI does not depend on user input, rendering or UI, therefore it may be
+//! used in headless automated matches.
+
+use bevy::{app::PluginGroupBuilder, prelude::*};
+use clock::ClockPlugin;
+use construction::ConstructionPlugin;
+use defense::DefensePlugin;
+use offense::OffensePlugin;
+pub use players::{AiPlayers, Difficulty};
+use production::ProductionPlugin;
+
+mod clock;
+mod construction;
+mod defense;
+mod offense;
+mod players;
+mod production;
+
+pub struct AiPluginGroup;
+
+impl PluginGroup for AiPluginGroup {
+    fn build(&mut self, group: &mut PluginGroupBuilder) {
+        group
+            .add(ClockPlugin)
+            .add(ConstructionPlugin)
+            .add(DefensePlugin)
+            .add(OffensePlugin)
+            .add(ProductionPlugin);
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemLabel)]
+enum AiLabels {
+    /// The AI decision clock is advanced in systems with this label.
+    Clock,
+}
@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use de_behaviour::{
+    AttackMove, ChaseTarget, Follow, Order, OrderEvent, OrderMode, OrderQueue, Patrol,
+};
+use de_core::{
+    objects::{MovableSolid, StaticSolid},
+    player::Player,
+    projection::ToFlat,
+    stages::GameStage,
+    state::GameState,
+};
+use de_map::size::MapBounds;
+use de_objects::Armament;
+use de_pathing::PathTarget;
+use de_vision::FogOfWar;
+use iyes_loopless::prelude::*;
+
+use crate::{clock::DecisionClock, players::AiPlayers, AiLabels};
+
+/// Scouting destinations are this far (in meters) from the map edges.
+const SCOUTING_MARGIN: f32 = 10.;
+
+pub(crate) struct OffensePlugin;
+
+impl Plugin for OffensePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            GameStage::PreUpdate,
+            attack
+                .run_in_state(GameState::Playing)
+                .after(AiLabels::Clock),
+        );
+    }
+}
+
+/// Armed units which neither execute nor have queued any order.
+type Idle<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Player,
+        &'static Transform,
+        &'static OrderQueue,
+    ),
+    (
+        With<MovableSolid>,
+        With<Armament>,
+        Without<PathTarget>,
+        Without<ChaseTarget>,
+        Without<AttackMove>,
+        Without<Patrol>,
+        Without<Follow>,
+    ),
+>;
+
+/// Once a computer player has enough idle armed units, they are sent in a
+/// single wave to the nearest known enemy building. A single unit is sent to
+/// scout the map while no enemy building is known.
+fn attack(
+    ai_players: Res<AiPlayers>,
+    clock: Res<DecisionClock>,
+    fog: Res<FogOfWar>,
+    bounds: Res<MapBounds>,
+    mut orders: EventWriter<OrderEvent>,
+    idle: Idle,
+    buildings: Query<(&Player, &Transform), With<StaticSolid>>,
+) {
+    for (player, difficulty) in ai_players.iter() {
+        if !clock.decides(difficulty) {
+            continue;
+        }
+
+        let units: Vec<(Entity, Vec2)> = idle
+            .iter()
+            .filter(|(_, &owner, _, queue)| owner == player && queue.is_empty())
+            .map(|(entity, _, transform, _)| (entity, transform.translation.to_flat()))
+            .collect();
+        if units.is_empty() {
+            continue;
+        }
+
+        let center = units.iter().map(|&(_, position)| position).sum::<Vec2>() / units.len() as f32;
+        let target = buildings
+            .iter()
+            .filter(|(&owner, _)| owner != player)
+            .map(|(_, transform)| transform.translation.to_flat())
+            .filter(|&position| difficulty.omniscient() || fog.is_explored(player, position))
+            .min_by(|a, b| {
+                a.distance_squared(center)
+                    .total_cmp(&b.distance_squared(center))
+            });
+
+        match target {
+            Some(target) => {
+                if units.len() < difficulty.wave_size() {
+                    continue;
+                }
+                for &(entity, _) in units.iter() {
+                    orders.send(OrderEvent::new(
+                        entity,
+                        Order::AttackMove(target),
+                        OrderMode::Replace,
+                    ));
+                }
+            }
+            None => {
+                let destination = match scouting_points(&bounds)
+                    .into_iter()
+                    .find(|&point| !fog.is_explored(player, point))
+                {
+                    Some(destination) => destination,
+                    None => continue,
+                };
+                orders.send(OrderEvent::new(
+                    units[0].0,
+                    Order::AttackMove(destination),
+                    OrderMode::Replace,
+                ));
+            }
+        }
+    }
+}
+
+/// Returns points which are visited by scouting units: corners of the map
+/// followed by its center.
+fn scouting_points(bounds: &MapBounds) -> [Vec2; 5] {
+    let min = bounds.min() + SCOUTING_MARGIN;
+    let max = bounds.max() - SCOUTING_MARGIN;
+    [
+        min,
+        Vec2::new(max.x, min.y),
+        max,
+        Vec2::new(min.x, max.y),
+        (min + max) / 2.,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scouting_points() {
+        let bounds = MapBounds::new(Vec2::new(100., 200.));
+        assert_eq!(
+            scouting_points(&bounds),
+            [
+                Vec2::new(-40., -90.),
+                Vec2::new(40., -90.),
+                Vec2::new(40., 90.),
+                Vec2::new(-40., 90.),
+                Vec2::ZERO,
+            ]
+        );
+    }
+}
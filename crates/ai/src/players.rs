@@ -0,0 +1,99 @@
+use ahash::AHashMap;
+use de_core::player::Player;
+
+/// Players controlled by the computer.
+#[derive(Default)]
+pub struct AiPlayers(AHashMap<Player, Difficulty>);
+
+impl AiPlayers {
+    pub fn new<I>(players: I) -> Self
+    where
+        I: IntoIterator<Item = (Player, Difficulty)>,
+    {
+        Self(players.into_iter().collect())
+    }
+
+    /// Returns difficulty of a computer controlled player or None if the
+    /// player is not controlled by the computer.
+    pub fn get(&self, player: Player) -> Option<Difficulty> {
+        self.0.get(&player).copied()
+    }
+
+    /// Returns an iterator over all computer controlled players and their
+    /// difficulties.
+    pub fn iter(&self) -> impl Iterator<Item = (Player, Difficulty)> + '_ {
+        self.0
+            .iter()
+            .map(|(&player, &difficulty)| (player, difficulty))
+    }
+}
+
+/// Difficulty level of a computer opponent.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Number of AI clock ticks between two consecutive decisions.
+    pub(crate) fn decision_period(self) -> u32 {
+        match self {
+            Self::Easy => 4,
+            Self::Normal => 2,
+            Self::Hard => 1,
+        }
+    }
+
+    /// Minimum number of idle units needed to launch an attack.
+    pub(crate) fn wave_size(self) -> usize {
+        match self {
+            Self::Easy => 8,
+            Self::Normal => 6,
+            Self::Hard => 4,
+        }
+    }
+
+    /// Maximum number of buildings the computer builds up to.
+    pub(crate) fn max_buildings(self) -> usize {
+        match self {
+            Self::Easy => 3,
+            Self::Normal => 6,
+            Self::Hard => 12,
+        }
+    }
+
+    /// Units this close (in meters) to an attacked building are sent to
+    /// defend it.
+    pub(crate) fn defense_radius(self) -> f32 {
+        match self {
+            Self::Easy => 30.,
+            Self::Normal => 50.,
+            Self::Hard => 80.,
+        }
+    }
+
+    /// Returns true if the computer knows positions of all enemy buildings,
+    /// i.e. including those which it has never seen.
+    pub(crate) fn omniscient(self) -> bool {
+        matches!(self, Self::Hard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ai_players() {
+        let players = AiPlayers::new([
+            (Player::Player2, Difficulty::Easy),
+            (Player::Player3, Difficulty::Hard),
+        ]);
+        assert_eq!(players.get(Player::Player1), None);
+        assert_eq!(players.get(Player::Player2), Some(Difficulty::Easy));
+        assert_eq!(players.get(Player::Player3), Some(Difficulty::Hard));
+        assert_eq!(players.iter().count(), 2);
+    }
+}
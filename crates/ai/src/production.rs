@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+use de_core::{player::Player, stages::GameStage, state::GameState};
+use de_objects::Factory;
+use de_production::{ProductionCommand, ProductionEvent, ProductionQueue};
+use iyes_loopless::prelude::*;
+
+use crate::{clock::DecisionClock, players::AiPlayers, AiLabels};
+
+pub(crate) struct ProductionPlugin;
+
+impl Plugin for ProductionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            GameStage::PreUpdate,
+            produce
+                .run_in_state(GameState::Playing)
+                .after(AiLabels::Clock),
+        );
+    }
+}
+
+/// Computer players keep producing units in all their buildings which are
+/// able to produce them. At most a single unit is queued in a building at a
+/// time so that resources are left for construction.
+fn produce(
+    ai_players: Res<AiPlayers>,
+    clock: Res<DecisionClock>,
+    mut events: EventWriter<ProductionEvent>,
+    factories: Query<(Entity, &Player, &Factory, &ProductionQueue)>,
+) {
+    for (entity, &player, factory, queue) in factories.iter() {
+        let difficulty = match ai_players.get(player) {
+            Some(difficulty) => difficulty,
+            None => continue,
+        };
+        if !clock.decides(difficulty) || !queue.is_empty() {
+            continue;
+        }
+
+        if let Some(&unit_type) = factory.products().first() {
+            events.send(ProductionEvent::new(
+                entity,
+                ProductionCommand::Enqueue(unit_type),
+            ));
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use de_core::{stages::GameStage, state::GameState};
+use de_pathing::PathTarget;
+use glam::Vec2;
+use iyes_loopless::prelude::*;
+
+use crate::chase::ChaseTarget;
+
+pub(crate) struct AttackMovePlugin;
+
+impl Plugin for AttackMovePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            GameStage::PostUpdate,
+            finish.run_in_state(GameState::Playing),
+        );
+    }
+}
+
+/// Objects with this component move towards a destination but they attack
+/// all enemies encountered on the way. Once there is no enemy to attack, they
+/// resume moving towards the destination.
+///
+/// The component is removed once the object arrives at the destination (or
+/// at its formation slot around the destination) or once the destination
+/// turns out to be unreachable.
+#[derive(Component)]
+pub struct AttackMove {
+    destination: Vec2,
+}
+
+impl AttackMove {
+    pub fn new(destination: Vec2) -> Self {
+        Self { destination }
+    }
+
+    pub fn destination(&self) -> Vec2 {
+        self.destination
+    }
+}
+
+fn finish(
+    mut commands: Commands,
+    objects: Query<Entity, (With<AttackMove>, Without<PathTarget>, Without<ChaseTarget>)>,
+) {
+    // Objects which stop chasing get their path target inserted right away,
+    // see chase::resume(), therefore objects without a path target are not
+    // moving anywhere.
+    for entity in objects.iter() {
+        commands.entity(entity).remove::<AttackMove>();
+    }
+}
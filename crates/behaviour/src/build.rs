@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use de_core::{
+    objects::{ActiveObjectType, BuildingType, ObjectType, PLAYER_MAX_BUILDINGS},
+    player::Player,
+    projection::ToMsl,
+    stages::GameStage,
+    state::GameState,
+};
+use de_economy::Stockpiles;
+use de_objects::ObjectCache;
+use de_spawner::{ObjectCounter, Placement, SpawnBundle};
+use glam::Vec2;
+use iyes_loopless::prelude::*;
+
+pub(crate) struct BuildPlugin;
+
+impl Plugin for BuildPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            GameStage::PostUpdate,
+            construct.run_in_state(GameState::Playing),
+        );
+    }
+}
+
+/// Units with this component place a new building (under construction) of
+/// their player.
+///
+/// The component is removed right away. The building is placed only if it
+/// could be placed from a draft at the same location (see
+/// [`de_spawner::Draft`]) and if the player can afford it.
+#[derive(Component)]
+pub(crate) struct Construct {
+    building_type: BuildingType,
+    location: Vec2,
+}
+
+impl Construct {
+    /// Creates a new construct component.
+    ///
+    /// # Arguments
+    ///
+    /// * `building_type` - type of the placed building.
+    ///
+    /// * `location` - position of the placed building.
+    pub(crate) fn new(building_type: BuildingType, location: Vec2) -> Self {
+        Self {
+            building_type,
+            location,
+        }
+    }
+}
+
+fn construct(
+    mut commands: Commands,
+    mut stockpiles: ResMut<Stockpiles>,
+    cache: Res<ObjectCache>,
+    counter: Res<ObjectCounter>,
+    placement: Placement,
+    units: Query<(Entity, &Player, &Construct)>,
+) {
+    for (entity, &player, construct) in units.iter() {
+        commands.entity(entity).remove::<Construct>();
+
+        if counter.building_count(player) >= PLAYER_MAX_BUILDINGS {
+            continue;
+        }
+
+        let object_type = ObjectType::Active(ActiveObjectType::Building(construct.building_type));
+        let transform = Transform::from_translation(construct.location.to_msl());
+        if placement.placeable(object_type, &transform, player)
+            && stockpiles.spend(player, cache.get(object_type).cost())
+        {
+            commands
+                .spawn_bundle(SpawnBundle::construction(object_type, transform))
+                .insert(player);
+        }
+    }
+}
@@ -1,8 +1,22 @@
 use bevy::prelude::*;
-use de_core::{projection::ToFlat, stages::GameStage, state::GameState};
+use de_core::{objects::MovableSolid, projection::ToFlat, stages::GameStage, state::GameState};
 use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPath};
 use iyes_loopless::prelude::*;
 
+use crate::{
+    attackmove::AttackMove,
+    follow::Follow,
+    patrol::Patrol,
+    stance::{Leash, Stance},
+};
+
+/// Multiple of armament range. The attacking entities will try to stay as close
+/// or further from attacked targets.
+const MIN_CHASE_DISTANCE: f32 = 0.4;
+/// Multiple of armament range. The attacking entities will try to stay as close
+/// or closer from attacked targets.
+pub const MAX_CHASE_DISTANCE: f32 = 0.9;
+
 pub(crate) struct ChasePlugin;
 
 impl Plugin for ChasePlugin {
@@ -15,6 +29,12 @@ impl Plugin for ChasePlugin {
 }
 
 /// Units with this component will chase the target entity.
+///
+/// Chasing is restricted by [`Stance`] of the unit. Units with [`Leash`] stop
+/// chasing once the target gets too far from the leash anchor and return back
+/// to the anchor, continue to their destination if they are on
+/// [`AttackMove`], or continue patrolling or following if they are on
+/// [`Patrol`] or [`Follow`].
 #[derive(Component)]
 pub struct ChaseTarget {
     entity: Entity,
@@ -54,6 +74,16 @@ impl ChaseTarget {
         }
     }
 
+    /// Creates a new chase target which keeps the chasing entity close
+    /// enough to attack the chased entity with a cannon of a given range.
+    pub fn within_range(entity: Entity, range: f32) -> Self {
+        Self::new(
+            entity,
+            MIN_CHASE_DISTANCE * range,
+            MAX_CHASE_DISTANCE * range,
+        )
+    }
+
     pub fn entity(&self) -> Entity {
         self.entity
     }
@@ -67,21 +97,102 @@ impl ChaseTarget {
     }
 }
 
+type ChasingQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static ChaseTarget,
+        Option<&'static PathTarget>,
+        Option<&'static Stance>,
+        Option<&'static Leash>,
+        Option<&'static MovableSolid>,
+        Option<&'static AttackMove>,
+        Option<&'static Patrol>,
+        Option<&'static Follow>,
+    ),
+>;
+
 fn chase(
     mut commands: Commands,
     mut path_events: EventWriter<UpdateEntityPath>,
-    chasing: Query<(Entity, &Transform, &ChaseTarget, Option<&PathTarget>)>,
+    chasing: ChasingQuery,
     targets: Query<&Transform>,
 ) {
-    for (entity, transform, chase_target, path_target) in chasing.iter() {
+    for (
+        entity,
+        transform,
+        chase_target,
+        path_target,
+        stance,
+        leash,
+        movable,
+        attack_move,
+        patrol,
+        follow,
+    ) in chasing.iter()
+    {
+        // Patrolling and following objects return to their route on their
+        // own.
+        let routed = patrol.is_some() || follow.is_some();
+        let stance = stance
+            .copied()
+            .unwrap_or_default()
+            .restricted(movable.is_some());
+
         let target_position = match targets.get(chase_target.entity()) {
             Ok(transform) => transform.translation.to_flat(),
             Err(_) => {
                 commands.entity(entity).remove::<ChaseTarget>();
+                if let Some(leash) = leash {
+                    if stance.may_chase() {
+                        resume(
+                            &mut commands,
+                            &mut path_events,
+                            entity,
+                            leash,
+                            attack_move,
+                            routed,
+                        );
+                    } else {
+                        // The object did not leave its position or its way,
+                        // there is nothing to return to.
+                        commands.entity(entity).remove::<Leash>();
+                    }
+                } else if !routed && path_target.map_or(false, |t| t.permanent()) {
+                    // Chasing path targets are permanent, the object would
+                    // otherwise never be considered idle again.
+                    commands.entity(entity).remove::<PathTarget>();
+                }
                 continue;
             }
         };
 
+        if let (Some(leash), Some(radius)) = (leash, stance.pursuit_radius()) {
+            if (target_position - leash.anchor()).length() > radius {
+                commands.entity(entity).remove::<ChaseTarget>();
+                resume(
+                    &mut commands,
+                    &mut path_events,
+                    entity,
+                    leash,
+                    attack_move,
+                    routed,
+                );
+                continue;
+            }
+        }
+
+        if !stance.may_chase() {
+            // The target cannot be attacked without moving.
+            let distance = (target_position - transform.translation.to_flat()).length();
+            if distance > chase_target.max_distance() {
+                commands.entity(entity).remove::<ChaseTarget>();
+            }
+            continue;
+        }
+
         let (path_target, distance) = path_target
             .map(|path_target| (path_target.location(), path_target.properties().distance()))
             .unwrap_or((transform.translation.to_flat(), 0.));
@@ -100,3 +211,28 @@ fn chase(
         ));
     }
 }
+
+/// Makes an entity which stopped chasing return to its leash anchor or
+/// continue to its attack-move destination. Nothing but the leash removal
+/// is done for `routed` entities, i.e. entities which return to their route
+/// on their own.
+fn resume(
+    commands: &mut Commands,
+    path_events: &mut EventWriter<UpdateEntityPath>,
+    entity: Entity,
+    leash: &Leash,
+    attack_move: Option<&AttackMove>,
+    routed: bool,
+) {
+    commands.entity(entity).remove::<Leash>();
+    if routed {
+        return;
+    }
+    let destination = attack_move.map_or(leash.anchor(), |attack_move| attack_move.destination());
+    let target = PathTarget::new(destination, PathQueryProps::exact(), false);
+    // The path target is inserted right away (i.e. before the path is
+    // computed) so that the object is not considered to be finished with its
+    // attack-move meanwhile.
+    commands.entity(entity).insert(target);
+    path_events.send(UpdateEntityPath::new(entity, target));
+}
@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+use de_core::{projection::ToFlat, stages::GameStage, state::GameState};
+use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPath};
+use glam::Vec2;
+use iyes_loopless::prelude::*;
+
+use crate::{chase::ChaseTarget, retreat::Retreat};
+
+/// Following units try to stay at least this far (in meters) from the
+/// followed entity.
+const MIN_FOLLOW_DISTANCE: f32 = 6.;
+/// Following units try to stay at most this far (in meters) from the
+/// followed entity.
+const MAX_FOLLOW_DISTANCE: f32 = 12.;
+
+pub(crate) struct FollowPlugin;
+
+impl Plugin for FollowPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(GameStage::Update, follow.run_in_state(GameState::Playing));
+    }
+}
+
+/// Units with this component follow a friendly entity at a distance. Unlike
+/// [`ChaseTarget`], the followed entity is never attacked.
+///
+/// Guarding units additionally attack enemies which damage the guarded
+/// entity. They resume following once there is no enemy to attack.
+///
+/// The component is removed once the followed entity no longer exists.
+#[derive(Component)]
+pub struct Follow {
+    entity: Entity,
+    guard: bool,
+}
+
+impl Follow {
+    /// Creates a new follow component.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - entity to follow.
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            guard: false,
+        }
+    }
+
+    /// Creates a new follow component which guards the followed entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - entity to follow and guard.
+    pub fn guard(entity: Entity) -> Self {
+        Self {
+            entity,
+            guard: true,
+        }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns true if the following unit attacks enemies which damage the
+    /// followed entity.
+    pub fn guards(&self) -> bool {
+        self.guard
+    }
+}
+
+fn follow(
+    mut commands: Commands,
+    mut path_events: EventWriter<UpdateEntityPath>,
+    following: Query<
+        (Entity, &Transform, &Follow, Option<&PathTarget>),
+        (Without<ChaseTarget>, Without<Retreat>),
+    >,
+    targets: Query<&Transform>,
+) {
+    for (entity, transform, follow, path_target) in following.iter() {
+        let target_position = match targets.get(follow.entity()) {
+            Ok(transform) => transform.translation.to_flat(),
+            Err(_) => {
+                let mut entity_commands = commands.entity(entity);
+                entity_commands.remove::<Follow>();
+                if path_target.is_some() {
+                    entity_commands.remove::<PathTarget>();
+                }
+                continue;
+            }
+        };
+
+        if let Some(target) = follow_target(
+            transform.translation.to_flat(),
+            path_target,
+            target_position,
+        ) {
+            path_events.send(UpdateEntityPath::new(entity, target));
+        }
+    }
+}
+
+/// Returns a new path target of a following unit or None if the current path
+/// target (or the current position if there is no path target) is close
+/// enough to the followed entity.
+///
+/// # Arguments
+///
+/// * `position` - current position of the following unit.
+///
+/// * `path_target` - current path target of the following unit.
+///
+/// * `target_position` - current position of the followed entity.
+fn follow_target(
+    position: Vec2,
+    path_target: Option<&PathTarget>,
+    target_position: Vec2,
+) -> Option<PathTarget> {
+    let (location, distance) = path_target
+        .map(|path_target| (path_target.location(), path_target.properties().distance()))
+        .unwrap_or((position, 0.));
+
+    if (target_position - location).length() + distance <= MAX_FOLLOW_DISTANCE {
+        return None;
+    }
+
+    // The path target is permanent so that the following unit is never
+    // considered idle, e.g. while the followed entity stands still.
+    Some(PathTarget::new(
+        target_position,
+        PathQueryProps::new(MIN_FOLLOW_DISTANCE, MAX_FOLLOW_DISTANCE),
+        true,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow() {
+        let follow = Follow::new(Entity::from_raw(1));
+        assert_eq!(follow.entity(), Entity::from_raw(1));
+        assert!(!follow.guards());
+
+        let guard = Follow::guard(Entity::from_raw(2));
+        assert_eq!(guard.entity(), Entity::from_raw(2));
+        assert!(guard.guards());
+    }
+
+    #[test]
+    fn test_follow_target() {
+        // The followed entity is close enough.
+        assert!(follow_target(Vec2::ZERO, None, Vec2::new(10., 0.)).is_none());
+
+        let target = follow_target(Vec2::ZERO, None, Vec2::new(20., 0.)).unwrap();
+        assert_eq!(target.location(), Vec2::new(20., 0.));
+        assert_eq!(target.properties().distance(), MIN_FOLLOW_DISTANCE);
+        assert!(target.permanent());
+
+        // The unit is already on its way to the followed entity.
+        assert!(follow_target(Vec2::ZERO, Some(&target), Vec2::new(22., 0.)).is_none());
+        // The followed entity moved away from the path target.
+        let updated = follow_target(Vec2::ZERO, Some(&target), Vec2::new(20., 10.)).unwrap();
+        assert_eq!(updated.location(), Vec2::new(20., 10.));
+    }
+}
@@ -1,15 +1,49 @@
 //! This crate implements various entity behavior systems.
 
+pub use attackmove::AttackMove;
+use attackmove::AttackMovePlugin;
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
+use build::BuildPlugin;
 use chase::ChasePlugin;
-pub use chase::ChaseTarget;
+pub use chase::{ChaseTarget, MAX_CHASE_DISTANCE};
+pub use follow::Follow;
+use follow::FollowPlugin;
+use orders::OrdersPlugin;
+pub use orders::{Order, OrderEvent, OrderMode, OrderQueue};
+pub use patrol::Patrol;
+use patrol::PatrolPlugin;
+use retreat::RetreatPlugin;
+pub use retreat::{Retreat, RETREAT_THRESHOLD};
+use stance::StancePlugin;
+pub use stance::{Leash, Stance};
+use tree::TreePlugin;
+pub use tree::{
+    AcquireTargetEvent, Action, BehaviourTree, BehaviourTrees, Condition, Node, Status,
+};
 
+mod attackmove;
+mod build;
 mod chase;
+mod follow;
+mod orders;
+mod patrol;
+mod retreat;
+mod stance;
+mod tree;
 
 pub struct BehaviourPluginGroup;
 
 impl PluginGroup for BehaviourPluginGroup {
     fn build(&mut self, group: &mut PluginGroupBuilder) {
-        group.add(ChasePlugin);
+        group
+            .add(ChasePlugin)
+            .add(StancePlugin)
+            .add(AttackMovePlugin)
+            .add(OrdersPlugin)
+            .add(PatrolPlugin)
+            .add(FollowPlugin)
+            .add(TreePlugin)
+            .add(RetreatPlugin)
+            .add(BuildPlugin);
     }
 }
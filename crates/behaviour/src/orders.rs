@@ -0,0 +1,448 @@
+use std::collections::VecDeque;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use de_core::{
+    objects::{BuildingType, MovableSolid, ObjectType},
+    projection::ToFlat,
+    stages::GameStage,
+    state::GameState,
+};
+use de_objects::Armament;
+use de_pathing::{Formation, PathQueryProps, PathTarget, UpdateEntityPath, UpdateGroupPath};
+use glam::Vec2;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    attackmove::AttackMove,
+    build::Construct,
+    chase::ChaseTarget,
+    follow::Follow,
+    patrol::{waypoint_target, Patrol},
+    retreat::Retreat,
+    stance::Leash,
+};
+
+pub(crate) struct OrdersPlugin;
+
+impl Plugin for OrdersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<OrderEvent>()
+            .add_system_to_stage(GameStage::Update, execute.run_in_state(GameState::Playing))
+            .add_system_to_stage(GameStage::PostUpdate, init.run_in_state(GameState::Playing));
+    }
+}
+
+/// An order given to a unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Order {
+    /// Move to a location.
+    Move(Vec2),
+    /// Attack an entity until it is destroyed or out of reach.
+    Attack(Entity),
+    /// Move to a location and attack enemies encountered on the way, see
+    /// [`AttackMove`].
+    AttackMove(Vec2),
+    /// Patrol between the location where the order is started and a
+    /// waypoint, see [`Patrol`]. Patrol orders appended to a patrolling unit
+    /// extend its patrol route.
+    ///
+    /// Patrol is finished only once none of its waypoints can be reached,
+    /// thus orders queued after it are usually executed only if the patrol is
+    /// canceled.
+    Patrol(Vec2),
+    /// Follow a friendly entity, see [`Follow`].
+    ///
+    /// Following is finished only once the followed entity no longer exists.
+    Follow(Entity),
+    /// Follow a friendly entity and attack enemies which damage it, see
+    /// [`Follow`].
+    Guard(Entity),
+    /// Place a new building (under construction) of a type at a location.
+    /// The building is placed only if a [`de_spawner::Draft`] could be
+    /// placed there and if the player of the unit can afford it. The unit
+    /// does not move to the location.
+    ///
+    /// The order is finished right away, even if the building cannot be
+    /// placed (e.g. because the location is occupied or the player cannot
+    /// afford the building).
+    Build(BuildingType, Vec2),
+}
+
+/// How a newly given order relates to already given orders.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OrderMode {
+    /// The current order and all queued orders are canceled and the new
+    /// order is executed immediately.
+    Replace,
+    /// The new order is executed after all already given orders are
+    /// finished.
+    Append,
+}
+
+/// Send this event to give an order to a unit.
+pub struct OrderEvent {
+    entity: Entity,
+    order: Order,
+    mode: OrderMode,
+}
+
+impl OrderEvent {
+    pub fn new(entity: Entity, order: Order, mode: OrderMode) -> Self {
+        Self {
+            entity,
+            order,
+            mode,
+        }
+    }
+}
+
+/// Groups of at most this many units move in a line formation.
+const MAX_LINE_GROUP: usize = 3;
+
+/// Orders which a unit executes once its current order is finished.
+///
+/// An order is finished once the unit is neither moving, nor chasing a target,
+/// nor attack-moving, nor patrolling nor following, e.g. once the unit
+/// arrives to its destination, once an attacked enemy is destroyed or once a
+/// path cannot be found.
+#[derive(Component, Default)]
+pub struct OrderQueue(VecDeque<Order>);
+
+impl OrderQueue {
+    /// Returns an iterator over queued orders in the order of their future
+    /// execution. The order being currently executed is not included.
+    pub fn iter(&self) -> impl Iterator<Item = &Order> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn push(&mut self, order: Order) {
+        self.0.push_back(order);
+    }
+
+    fn pop(&mut self) -> Option<Order> {
+        self.0.pop_front()
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+type Units<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static mut OrderQueue,
+        Option<&'static PathTarget>,
+        Option<&'static mut Patrol>,
+    ),
+>;
+
+/// Units which execute an order other than a plain move or which retreat.
+type Active<'w, 's> = Query<
+    'w,
+    's,
+    (),
+    Or<(
+        With<ChaseTarget>,
+        With<AttackMove>,
+        With<Patrol>,
+        With<Follow>,
+        With<Retreat>,
+    )>,
+>;
+
+#[derive(SystemParam)]
+struct PathEvents<'w, 's> {
+    entity: EventWriter<'w, 's, UpdateEntityPath>,
+    group: EventWriter<'w, 's, UpdateGroupPath>,
+}
+
+/// Units which were given the same move or attack-move order at once.
+struct MoveGroup {
+    destination: Vec2,
+    attack: bool,
+    entities: Vec<Entity>,
+}
+
+impl MoveGroup {
+    /// Starts execution of the order. Groups of more than one unit move in a
+    /// formation.
+    fn start(self, commands: &mut Commands, path_events: &mut PathEvents) {
+        if self.attack {
+            for &entity in self.entities.iter() {
+                commands
+                    .entity(entity)
+                    .insert(AttackMove::new(self.destination));
+            }
+        }
+
+        if self.entities.len() == 1 {
+            move_to(commands, path_events, self.entities[0], self.destination);
+            return;
+        }
+
+        let formation = if self.entities.len() <= MAX_LINE_GROUP {
+            Formation::Line
+        } else if self.attack {
+            Formation::Wedge
+        } else {
+            Formation::Box
+        };
+
+        let target = PathTarget::new(self.destination, PathQueryProps::exact(), false);
+        // See move_to().
+        for &entity in self.entities.iter() {
+            commands.entity(entity).insert(target);
+        }
+        path_events
+            .group
+            .send(UpdateGroupPath::new(self.entities, target, formation));
+    }
+}
+
+fn init(mut commands: Commands, units: Query<Entity, (Added<MovableSolid>, Without<OrderQueue>)>) {
+    for entity in units.iter() {
+        commands.entity(entity).insert(OrderQueue::default());
+    }
+}
+
+fn execute(
+    mut commands: Commands,
+    mut events: EventReader<OrderEvent>,
+    mut path_events: PathEvents,
+    mut units: Units,
+    active: Active,
+    armaments: Query<&Armament>,
+    targets: Query<&ObjectType>,
+) {
+    // Components inserted or removed during execution of an order are not
+    // visible until the end of the stage, therefore these entities must not
+    // be considered idle.
+    let mut busy = Vec::new();
+    let mut groups: Vec<MoveGroup> = Vec::new();
+
+    for event in events.iter() {
+        let (entity, transform, mut queue, path, patrol) = match units.get_mut(event.entity) {
+            Ok(unit) => unit,
+            Err(_) => continue,
+        };
+
+        match (event.mode, event.order, patrol) {
+            (OrderMode::Replace, order, _) => {
+                // Current orders are kept when the new order is invalid.
+                if !executable(&armaments, &targets, entity, order) {
+                    continue;
+                }
+
+                queue.clear();
+                busy.push(entity);
+                cancel(&mut commands, entity, path);
+
+                let (destination, attack) = match order {
+                    Order::Move(destination) => (destination, false),
+                    Order::AttackMove(destination) => (destination, true),
+                    _ => {
+                        start(
+                            &mut commands,
+                            &mut path_events,
+                            &armaments,
+                            &targets,
+                            entity,
+                            transform.translation.to_flat(),
+                            order,
+                        );
+                        continue;
+                    }
+                };
+
+                match groups
+                    .iter_mut()
+                    .find(|group| group.destination == destination && group.attack == attack)
+                {
+                    Some(group) => group.entities.push(entity),
+                    None => groups.push(MoveGroup {
+                        destination,
+                        attack,
+                        entities: vec![entity],
+                    }),
+                }
+            }
+            (OrderMode::Append, Order::Patrol(waypoint), Some(mut patrol)) if queue.is_empty() => {
+                patrol.push(waypoint);
+            }
+            (OrderMode::Append, order, _) => queue.push(order),
+        }
+    }
+
+    for group in groups {
+        group.start(&mut commands, &mut path_events);
+    }
+
+    for (entity, transform, mut queue, path, _) in units.iter_mut() {
+        if busy.contains(&entity) || path.is_some() || active.contains(entity) {
+            continue;
+        }
+
+        while let Some(order) = queue.pop() {
+            if start(
+                &mut commands,
+                &mut path_events,
+                &armaments,
+                &targets,
+                entity,
+                transform.translation.to_flat(),
+                order,
+            ) {
+                break;
+            }
+        }
+    }
+}
+
+/// Stops execution of the current order.
+fn cancel(commands: &mut Commands, entity: Entity, path: Option<&PathTarget>) {
+    let mut entity_commands = commands.entity(entity);
+    // Permanent path targets (e.g. of chasing or patrolling units) are never
+    // removed by the path finder.
+    if path.map_or(false, |path| path.permanent()) {
+        entity_commands.remove::<PathTarget>();
+    }
+    // Only present components are removed.
+    entity_commands.remove_bundle::<(Leash, ChaseTarget, AttackMove, Patrol, Follow, Retreat)>();
+}
+
+/// Returns true if an order can be executed by an entity, e.g. false if the
+/// attacked entity no longer exists.
+fn executable(
+    armaments: &Query<&Armament>,
+    targets: &Query<&ObjectType>,
+    entity: Entity,
+    order: Order,
+) -> bool {
+    match order {
+        Order::Move(_) | Order::Patrol(_) | Order::AttackMove(_) | Order::Build(_, _) => true,
+        Order::Follow(target) | Order::Guard(target) => {
+            target != entity && targets.contains(target)
+        }
+        Order::Attack(enemy) => attack_range(armaments, targets, entity, enemy).is_some(),
+    }
+}
+
+/// Returns range of the entity's cannons against the enemy or None if the
+/// enemy cannot be attacked by the entity.
+fn attack_range(
+    armaments: &Query<&Armament>,
+    targets: &Query<&ObjectType>,
+    entity: Entity,
+    enemy: Entity,
+) -> Option<f32> {
+    match (armaments.get(entity), targets.get(enemy)) {
+        (Ok(armament), Ok(&target_type)) => armament.range(target_type),
+        _ => None,
+    }
+}
+
+/// Starts execution of an order. Returns false if the order cannot be
+/// executed, see [`executable`].
+fn start(
+    commands: &mut Commands,
+    path_events: &mut PathEvents,
+    armaments: &Query<&Armament>,
+    targets: &Query<&ObjectType>,
+    entity: Entity,
+    position: Vec2,
+    order: Order,
+) -> bool {
+    if !executable(armaments, targets, entity, order) {
+        return false;
+    }
+
+    match order {
+        Order::Move(destination) => {
+            move_to(commands, path_events, entity, destination);
+        }
+        Order::Patrol(waypoint) => {
+            let target = waypoint_target(waypoint);
+            commands
+                .entity(entity)
+                .insert(Patrol::new(vec![waypoint, position]))
+                .insert(target);
+            path_events
+                .entity
+                .send(UpdateEntityPath::new(entity, target));
+        }
+        Order::AttackMove(destination) => {
+            commands.entity(entity).insert(AttackMove::new(destination));
+            move_to(commands, path_events, entity, destination);
+        }
+        Order::Follow(target) => {
+            commands.entity(entity).insert(Follow::new(target));
+        }
+        Order::Guard(target) => {
+            commands.entity(entity).insert(Follow::guard(target));
+        }
+        Order::Build(building_type, location) => {
+            commands
+                .entity(entity)
+                .insert(Construct::new(building_type, location));
+        }
+        Order::Attack(enemy) => {
+            if let Some(range) = attack_range(armaments, targets, entity, enemy) {
+                commands
+                    .entity(entity)
+                    .insert(ChaseTarget::within_range(enemy, range));
+            }
+        }
+    }
+
+    true
+}
+
+fn move_to(
+    commands: &mut Commands,
+    path_events: &mut PathEvents,
+    entity: Entity,
+    destination: Vec2,
+) {
+    let target = PathTarget::new(destination, PathQueryProps::exact(), false);
+    // The path target is inserted right away (i.e. before the path is
+    // computed) so that the order is not considered finished meanwhile.
+    commands.entity(entity).insert(target);
+    path_events
+        .entity
+        .send(UpdateEntityPath::new(entity, target));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue() {
+        let mut queue = OrderQueue::default();
+        assert!(queue.is_empty());
+
+        queue.push(Order::Move(Vec2::new(1., 2.)));
+        queue.push(Order::Attack(Entity::from_raw(3)));
+        assert_eq!(
+            queue.iter().copied().collect::<Vec<Order>>(),
+            vec![
+                Order::Move(Vec2::new(1., 2.)),
+                Order::Attack(Entity::from_raw(3))
+            ]
+        );
+
+        assert_eq!(queue.pop(), Some(Order::Move(Vec2::new(1., 2.))));
+        assert!(!queue.is_empty());
+        queue.clear();
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+    }
+}
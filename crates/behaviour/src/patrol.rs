@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+use de_core::{projection::ToFlat, stages::GameStage, state::GameState};
+use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPath};
+use glam::Vec2;
+use iyes_loopless::prelude::*;
+
+use crate::{chase::ChaseTarget, retreat::Retreat};
+
+/// Patrolling objects closer than this (in meters) to their current waypoint
+/// are considered to have arrived.
+const ARRIVAL_DISTANCE: f32 = 4.;
+/// Paths to waypoints may finish up to this distance (in meters) from the
+/// waypoint, e.g. when the waypoint itself is inaccessible.
+const WAYPOINT_TOLERANCE: f32 = 3.;
+
+pub(crate) struct PatrolPlugin;
+
+impl Plugin for PatrolPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(GameStage::Update, patrol.run_in_state(GameState::Playing));
+    }
+}
+
+/// Objects with this component indefinitely cycle between two or more
+/// waypoints. They attack enemies encountered on the way according to their
+/// [`crate::Stance`] and resume patrolling once there is no enemy to attack.
+///
+/// Unreachable waypoints are skipped. The component is removed once none of
+/// the waypoints can be reached.
+#[derive(Component)]
+pub struct Patrol {
+    waypoints: Vec<Vec2>,
+    next: usize,
+    /// Number of consecutive waypoints which were not reached.
+    missed: usize,
+}
+
+impl Patrol {
+    /// Creates a new patrol starting at the first waypoint.
+    ///
+    /// # Panics
+    ///
+    /// May panic if there are less than two waypoints.
+    pub fn new(waypoints: Vec<Vec2>) -> Self {
+        debug_assert!(waypoints.len() >= 2);
+        Self {
+            waypoints,
+            next: 0,
+            missed: 0,
+        }
+    }
+
+    pub fn waypoints(&self) -> &[Vec2] {
+        self.waypoints.as_slice()
+    }
+
+    /// Returns the waypoint the object is currently heading to.
+    pub fn destination(&self) -> Vec2 {
+        self.waypoints[self.next]
+    }
+
+    /// Appends a waypoint to the end of the patrol route.
+    pub fn push(&mut self, waypoint: Vec2) {
+        self.waypoints.push(waypoint);
+    }
+
+    /// Makes the next waypoint, or the first waypoint after the last one, the
+    /// destination.
+    fn advance(&mut self) {
+        self.next = (self.next + 1) % self.waypoints.len();
+    }
+
+    /// Makes the next waypoint the destination once the object stopped
+    /// moving towards the current waypoint, i.e. once it arrived there or
+    /// once the waypoint turned out to be unreachable.
+    ///
+    /// Returns false if none of the waypoints was reached during the last
+    /// cycle of the patrol.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - current position of the patrolling object.
+    fn finish_leg(&mut self, position: Vec2) -> bool {
+        if (self.destination() - position).length() <= ARRIVAL_DISTANCE {
+            self.missed = 0;
+        } else {
+            self.missed += 1;
+        }
+        self.advance();
+        self.missed < self.waypoints.len()
+    }
+}
+
+/// Returns a path target to a patrol waypoint. The path target is removed
+/// once the object arrives near the waypoint or once no path is found.
+pub(crate) fn waypoint_target(waypoint: Vec2) -> PathTarget {
+    PathTarget::new(waypoint, PathQueryProps::new(0., WAYPOINT_TOLERANCE), false)
+}
+
+fn patrol(
+    mut commands: Commands,
+    mut path_events: EventWriter<UpdateEntityPath>,
+    mut patrolling: Query<
+        (Entity, &Transform, &mut Patrol, Option<&PathTarget>),
+        (Without<ChaseTarget>, Without<Retreat>),
+    >,
+) {
+    for (entity, transform, mut patrol, path_target) in patrolling.iter_mut() {
+        match path_target {
+            Some(target) if target.location() == patrol.destination() => continue,
+            // The path target leads elsewhere, e.g. to a previously chased
+            // enemy.
+            Some(_) => (),
+            // The object either arrived at the waypoint or the waypoint is
+            // unreachable.
+            None => {
+                if !patrol.finish_leg(transform.translation.to_flat()) {
+                    commands.entity(entity).remove::<Patrol>();
+                    continue;
+                }
+            }
+        }
+
+        let target = waypoint_target(patrol.destination());
+        // The path target is inserted right away so that the object is not
+        // considered to be finished with the leg before the path is computed.
+        commands.entity(entity).insert(target);
+        path_events.send(UpdateEntityPath::new(entity, target));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patrol() {
+        let mut patrol = Patrol::new(vec![Vec2::new(1., 2.), Vec2::new(3., 4.)]);
+        assert_eq!(patrol.destination(), Vec2::new(1., 2.));
+        patrol.advance();
+        assert_eq!(patrol.destination(), Vec2::new(3., 4.));
+        patrol.push(Vec2::new(5., 6.));
+        patrol.advance();
+        assert_eq!(patrol.destination(), Vec2::new(5., 6.));
+        patrol.advance();
+        assert_eq!(patrol.destination(), Vec2::new(1., 2.));
+        assert_eq!(patrol.waypoints().len(), 3);
+    }
+
+    #[test]
+    fn test_waypoint_cycling() {
+        let waypoints = vec![Vec2::new(0., 0.), Vec2::new(100., 0.), Vec2::new(0., 100.)];
+        let mut patrol = Patrol::new(waypoints.clone());
+
+        for i in 0..7 {
+            let reached = waypoints[i % 3];
+            assert_eq!(patrol.destination(), reached);
+            assert!(patrol.finish_leg(reached + Vec2::new(1., 1.)));
+        }
+        assert_eq!(patrol.destination(), waypoints[1]);
+    }
+
+    #[test]
+    fn test_unreachable_waypoint() {
+        let mut patrol = Patrol::new(vec![
+            Vec2::new(0., 0.),
+            Vec2::new(100., 0.),
+            Vec2::new(0., 100.),
+        ]);
+
+        assert!(patrol.finish_leg(Vec2::new(0., 0.)));
+        // The unit got stuck far from the second waypoint.
+        assert!(patrol.finish_leg(Vec2::new(50., 0.)));
+        assert_eq!(patrol.destination(), Vec2::new(0., 100.));
+        assert!(patrol.finish_leg(Vec2::new(0., 99.)));
+        assert_eq!(patrol.destination(), Vec2::new(0., 0.));
+
+        // None of the waypoints is reachable.
+        assert!(patrol.finish_leg(Vec2::new(50., 50.)));
+        assert!(patrol.finish_leg(Vec2::new(50., 50.)));
+        assert!(!patrol.finish_leg(Vec2::new(50., 50.)));
+    }
+}
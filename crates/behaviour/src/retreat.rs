@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use de_core::{stages::GameStage, state::GameState};
+use de_objects::Health;
+use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPath};
+use glam::Vec2;
+use iyes_loopless::prelude::*;
+
+/// Units retreat once their health fraction (see [`Health::fraction`]) drops
+/// below this threshold, see [`crate::Node::retreat`].
+pub const RETREAT_THRESHOLD: f32 = 0.25;
+/// Retreating units try to stay at least this far (in meters) from the
+/// center of their haven.
+const MIN_HAVEN_DISTANCE: f32 = 15.;
+/// Retreating units try to stay at most this far (in meters) from the center
+/// of their haven.
+const MAX_HAVEN_DISTANCE: f32 = 25.;
+
+pub(crate) struct RetreatPlugin;
+
+impl Plugin for RetreatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(GameStage::Update, recover.run_in_state(GameState::Playing));
+    }
+}
+
+/// Units with this component disengaged from combat and retreat to a
+/// friendly repair point (the haven), see [`crate::Action::Retreat`].
+///
+/// Havens are objects of the same player with a [`de_objects::Repairer`]
+/// component, i.e. fully constructed buildings whose type repairs nearby
+/// objects (currently only the Base).
+///
+/// Once fully healed (or once the haven no longer exists), the component is
+/// removed and the units resume their previous orders.
+#[derive(Component)]
+pub struct Retreat {
+    haven: Entity,
+    previous: Option<PathTarget>,
+}
+
+impl Retreat {
+    /// # Arguments
+    ///
+    /// * `haven` - entity to which the unit retreats.
+    ///
+    /// * `previous` - path target of the unit before the retreat. It is
+    ///   restored once the retreat is over. The path target of the unit is
+    ///   removed if this is None.
+    pub(crate) fn new(haven: Entity, previous: Option<PathTarget>) -> Self {
+        Self { haven, previous }
+    }
+
+    pub fn haven(&self) -> Entity {
+        self.haven
+    }
+}
+
+/// Returns a path target leading to a haven at a given location. The path
+/// target is permanent so that the retreating unit is never considered idle.
+pub(crate) fn haven_target(location: Vec2) -> PathTarget {
+    PathTarget::new(
+        location,
+        PathQueryProps::new(MIN_HAVEN_DISTANCE, MAX_HAVEN_DISTANCE),
+        true,
+    )
+}
+
+fn recover(
+    mut commands: Commands,
+    mut path_events: EventWriter<UpdateEntityPath>,
+    retreating: Query<(Entity, &Retreat, &Health)>,
+    havens: Query<(), With<Transform>>,
+) {
+    for (entity, retreat, health) in retreating.iter() {
+        if health.damaged() && havens.contains(retreat.haven()) {
+            continue;
+        }
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<Retreat>();
+        match retreat.previous {
+            Some(target) => {
+                entity_commands.insert(target);
+                path_events.send(UpdateEntityPath::new(entity, target));
+            }
+            None => {
+                entity_commands.remove::<PathTarget>();
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use de_core::{objects::MovableSolid, projection::ToFlat, stages::GameStage, state::GameState};
+use de_objects::Armament;
+use enum_map::Enum;
+use glam::Vec2;
+use iyes_loopless::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Maximum distance (in meters) between a defensive unit's leash anchor and
+/// a chased enemy.
+const DEFENSIVE_PURSUIT_RADIUS: f32 = 40.;
+
+pub(crate) struct StancePlugin;
+
+impl Plugin for StancePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(GameStage::PostUpdate, init.run_in_state(GameState::Playing));
+    }
+}
+
+/// Stance of an armed object. It controls behaviour of the object when it
+/// has no explicit orders or when it was not explicitly ordered to attack.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Enum, Serialize, Deserialize)]
+pub enum Stance {
+    /// The object never attacks on its own.
+    HoldFire,
+    /// The object attacks enemies in range but it never moves to chase them.
+    HoldPosition,
+    /// The object attacks nearby enemies and it chases them up to a limited
+    /// distance, after which it returns.
+    #[default]
+    Defensive,
+    /// The object attacks nearby enemies and it chases them without limits.
+    Aggressive,
+}
+
+impl Stance {
+    /// Returns true if the object automatically looks for enemies and
+    /// retaliates when attacked.
+    pub fn auto_acquires(self) -> bool {
+        !matches!(self, Self::HoldFire)
+    }
+
+    /// Returns true if the object may move to chase its target.
+    pub fn may_chase(self) -> bool {
+        !matches!(self, Self::HoldPosition)
+    }
+
+    /// Returns the stance restricted to capabilities of an object. Objects
+    /// which cannot move (e.g. turrets) hold their position.
+    pub fn restricted(self, movable: bool) -> Self {
+        if !movable && self.may_chase() {
+            Self::HoldPosition
+        } else {
+            self
+        }
+    }
+
+    /// Returns maximum distance between leash anchor of the object and its
+    /// chased target. None means that the distance is unlimited.
+    ///
+    /// See [`Leash`].
+    pub fn pursuit_radius(self) -> Option<f32> {
+        match self {
+            Self::Defensive => Some(DEFENSIVE_PURSUIT_RADIUS),
+            _ => None,
+        }
+    }
+}
+
+/// Position to which an object returns after it stops chasing an
+/// automatically acquired target.
+#[derive(Component)]
+pub struct Leash {
+    anchor: Vec2,
+}
+
+impl Leash {
+    pub fn new(anchor: Vec2) -> Self {
+        Self { anchor }
+    }
+
+    pub fn anchor(&self) -> Vec2 {
+        self.anchor
+    }
+}
+
+impl From<&Transform> for Leash {
+    fn from(transform: &Transform) -> Self {
+        Self::new(transform.translation.to_flat())
+    }
+}
+
+fn init(
+    mut commands: Commands,
+    armed: Query<(Entity, Option<&MovableSolid>), (Added<Armament>, Without<Stance>)>,
+) {
+    for (entity, movable) in armed.iter() {
+        commands
+            .entity(entity)
+            .insert(Stance::default().restricted(movable.is_some()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stance() {
+        assert!(!Stance::HoldFire.auto_acquires());
+        assert!(Stance::HoldPosition.auto_acquires());
+        assert!(!Stance::HoldPosition.may_chase());
+        assert!(Stance::Defensive.may_chase());
+        assert!(Stance::Defensive.pursuit_radius().is_some());
+        assert!(Stance::Aggressive.pursuit_radius().is_none());
+        assert_eq!(Stance::Aggressive.restricted(true), Stance::Aggressive);
+        assert_eq!(Stance::Aggressive.restricted(false), Stance::HoldPosition);
+        assert_eq!(Stance::HoldFire.restricted(false), Stance::HoldFire);
+    }
+}
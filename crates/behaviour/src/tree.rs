@@ -0,0 +1,620 @@
+//! Behaviour trees composing reusable conditions and actions into behaviour
+//! of individual objects.
+//!
+//! Trees are stateless: the whole tree of each object is re-evaluated from
+//! its root in regular intervals. Actions are executed via the same
+//! components and events as explicit orders (e.g. [`ChaseTarget`] or
+//! [`UpdateEntityPath`]), thus running actions are continued by the
+//! respective behaviour systems between evaluations.
+
+use std::{sync::Arc, time::Duration};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use de_core::{
+    objects::{Active, ActiveObjectType, MovableSolid, ObjectType},
+    player::Player,
+    projection::ToFlat,
+    stages::GameStage,
+    state::GameState,
+};
+use de_index::SpatialQuery;
+use de_objects::{Armament, Health, Repairer};
+use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPath};
+use enum_map::{enum_map, EnumMap};
+use glam::Vec2;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    attackmove::AttackMove,
+    chase::ChaseTarget,
+    follow::Follow,
+    orders::OrderQueue,
+    patrol::Patrol,
+    retreat::{haven_target, Retreat, RETREAT_THRESHOLD},
+    stance::{Leash, Stance},
+};
+
+/// Behaviour trees are evaluated this often.
+const EVALUATION_INTERVAL: Duration = Duration::from_millis(250);
+/// Objects closer (in meters) than this to the destination of a move action
+/// are considered to have arrived.
+const ARRIVAL_DISTANCE: f32 = 2.;
+
+pub(crate) struct TreePlugin;
+
+impl Plugin for TreePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BehaviourTrees>()
+            .add_event::<AcquireTargetEvent>()
+            .add_system_to_stage(GameStage::Update, evaluate.run_in_state(GameState::Playing))
+            .add_system_to_stage(GameStage::PostUpdate, init.run_in_state(GameState::Playing));
+    }
+}
+
+/// Behaviour trees of individual object types. Newly spawned active objects
+/// get the tree of their type, see [`BehaviourTree`].
+///
+/// By default, all units get a tree which makes them retreat (see
+/// [`Node::retreat`] and [`RETREAT_THRESHOLD`]) and attack enemies during
+/// attack-move (see [`Node::attack_move`]). Buildings get no tree.
+pub struct BehaviourTrees(EnumMap<ActiveObjectType, Option<BehaviourTree>>);
+
+impl BehaviourTrees {
+    /// Sets behaviour tree of an object type. Already spawned objects are not
+    /// affected.
+    ///
+    /// Units whose tree does not include [`Node::attack_move`] do not attack
+    /// enemies while they are on attack-move.
+    pub fn set(&mut self, object_type: ActiveObjectType, tree: BehaviourTree) {
+        self.0[object_type] = Some(tree);
+    }
+
+    pub fn get(&self, object_type: ActiveObjectType) -> Option<&BehaviourTree> {
+        self.0[object_type].as_ref()
+    }
+}
+
+impl Default for BehaviourTrees {
+    fn default() -> Self {
+        Self(enum_map! {
+            ActiveObjectType::Unit(_) => Some(BehaviourTree::new(Node::Selector(vec![
+                Node::retreat(RETREAT_THRESHOLD),
+                Node::attack_move(),
+            ]))),
+            ActiveObjectType::Building(_) => None,
+        })
+    }
+}
+
+/// This event is sent when an armed object should attack an enemy of its own
+/// choice. The enemy is chosen in the same way and under the same conditions
+/// as enemies attacked by idle objects, e.g. with respect to the object's
+/// [`Stance`]. Nothing happens if there is no suitable enemy.
+pub struct AcquireTargetEvent(Entity);
+
+impl AcquireTargetEvent {
+    fn new(entity: Entity) -> Self {
+        Self(entity)
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.0
+    }
+}
+
+/// Behaviour tree of an object. The tree is shared among objects and cheap
+/// to clone.
+#[derive(Component, Clone)]
+pub struct BehaviourTree(Arc<Node>);
+
+impl BehaviourTree {
+    pub fn new(root: Node) -> Self {
+        Self(Arc::new(root))
+    }
+
+    pub fn root(&self) -> &Node {
+        self.0.as_ref()
+    }
+}
+
+/// Result of an evaluation of a behaviour tree node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    Failure,
+    /// The node has not finished yet.
+    Running,
+}
+
+impl From<bool> for Status {
+    fn from(value: bool) -> Self {
+        if value {
+            Self::Success
+        } else {
+            Self::Failure
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    /// Evaluates its children one by one until one of them does not
+    /// succeed. The status of the last evaluated child is returned.
+    Sequence(Vec<Node>),
+    /// Evaluates its children one by one until one of them does not fail.
+    /// The status of the last evaluated child is returned.
+    Selector(Vec<Node>),
+    /// Swaps success and failure of its child.
+    Invert(Box<Node>),
+    /// Succeeds if the condition holds, fails otherwise.
+    Condition(Condition),
+    Action(Action),
+}
+
+impl Node {
+    /// Returns a node which makes the object retreat once its health
+    /// fraction (see [`Health::fraction`]) drops below `threshold`.
+    pub fn retreat(threshold: f32) -> Self {
+        Self::Sequence(vec![
+            Self::Condition(Condition::HealthBelow(threshold)),
+            Self::Action(Action::Retreat),
+        ])
+    }
+
+    /// Returns a node which makes an attack-moving object (see
+    /// [`AttackMove`]) attack enemies it encounters on its way. The object
+    /// continues to its destination once it stops attacking.
+    pub fn attack_move() -> Self {
+        Self::Sequence(vec![
+            Self::Condition(Condition::AttackMoving),
+            Self::Action(Action::AttackNearest),
+        ])
+    }
+
+    pub(crate) fn evaluate<A: Agent>(&self, agent: &mut A) -> Status {
+        match self {
+            Self::Sequence(children) => {
+                for child in children {
+                    let status = child.evaluate(agent);
+                    if status != Status::Success {
+                        return status;
+                    }
+                }
+                Status::Success
+            }
+            Self::Selector(children) => {
+                for child in children {
+                    let status = child.evaluate(agent);
+                    if status != Status::Failure {
+                        return status;
+                    }
+                }
+                Status::Failure
+            }
+            Self::Invert(child) => match child.evaluate(agent) {
+                Status::Success => Status::Failure,
+                Status::Failure => Status::Success,
+                Status::Running => Status::Running,
+            },
+            Self::Condition(condition) => agent.check(*condition).into(),
+            Self::Action(action) => agent.act(*action),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Condition {
+    /// An enemy active object is within a distance (in meters).
+    EnemyInRange(f32),
+    /// Health fraction (see [`Health::fraction`]) of the object is below a
+    /// threshold.
+    HealthBelow(f32),
+    /// The object is attacking (chasing) an entity.
+    Attacking,
+    /// The object is on attack-move, see [`AttackMove`].
+    AttackMoving,
+    /// The object neither executes nor has queued any order.
+    Idle,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Action {
+    /// Attack an enemy within range of the object's armament, usually the
+    /// nearest one, see [`AcquireTargetEvent`]. The object is leashed to its
+    /// current position, see [`Leash`].
+    ///
+    /// The action is running while the object is attacking or looking for an
+    /// enemy. It fails if the object cannot attack on its own, e.g. due to its
+    /// [`Stance`]. Attack-moving objects attack regardless of their stance.
+    AttackNearest,
+    /// Move to a location. The action is running until the object arrives.
+    MoveTo(Vec2),
+    /// Stop attacking and retreat to the nearest friendly repair point, see
+    /// [`Retreat`] for which objects are considered repair points. Plain move
+    /// orders are respected, i.e. the object does not retreat while it is
+    /// moving due to such an order.
+    ///
+    /// The action is running while the object is retreating and it fails if
+    /// there is no friendly repair point.
+    Retreat,
+}
+
+/// An object whose behaviour tree is being evaluated.
+pub(crate) trait Agent {
+    fn check(&mut self, condition: Condition) -> bool;
+
+    fn act(&mut self, action: Action) -> Status;
+}
+
+fn init(
+    mut commands: Commands,
+    trees: Res<BehaviourTrees>,
+    objects: Query<(Entity, &ObjectType), (Added<Active>, Without<BehaviourTree>)>,
+) {
+    for (entity, &object_type) in objects.iter() {
+        let active_type = match object_type {
+            ObjectType::Active(active_type) => active_type,
+            ObjectType::Inactive(_) => continue,
+        };
+        if let Some(tree) = trees.get(active_type) {
+            commands.entity(entity).insert(tree.clone());
+        }
+    }
+}
+
+type Agents<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static BehaviourTree,
+        &'static Transform,
+        &'static Player,
+        Option<&'static Health>,
+        Option<&'static Armament>,
+        Option<&'static Stance>,
+        Option<&'static MovableSolid>,
+        Option<&'static ChaseTarget>,
+        Option<&'static Leash>,
+        Option<&'static PathTarget>,
+        Option<&'static OrderQueue>,
+        Option<&'static AttackMove>,
+        Option<&'static Retreat>,
+    ),
+>;
+
+/// Parts of the world which behaviour tree nodes observe or act upon.
+#[derive(SystemParam)]
+struct Surroundings<'w, 's> {
+    commands: Commands<'w, 's>,
+    path_events: EventWriter<'w, 's, UpdateEntityPath>,
+    acquire_events: EventWriter<'w, 's, AcquireTargetEvent>,
+    objects: SpatialQuery<'w, 's, &'static Player>,
+    routed: Query<'w, 's, (), Or<(With<AttackMove>, With<Patrol>, With<Follow>)>>,
+    havens: Query<'w, 's, (Entity, &'static Player, &'static Transform), With<Repairer>>,
+}
+
+struct EcsAgent<'a, 'w, 's> {
+    surroundings: &'a mut Surroundings<'w, 's>,
+    entity: Entity,
+    transform: &'a Transform,
+    player: Player,
+    health: Option<&'a Health>,
+    armed: bool,
+    /// Whether the object's stance allows it to attack on its own.
+    acquires: bool,
+    chasing: bool,
+    anchor: Option<Vec2>,
+    path: Option<&'a PathTarget>,
+    queue: Option<&'a OrderQueue>,
+    attack_move: Option<&'a AttackMove>,
+    retreating: bool,
+}
+
+impl<'a, 'w, 's> EcsAgent<'a, 'w, 's> {
+    /// Returns true if there is an enemy object within a distance.
+    fn enemy_within(&self, radius: f32) -> bool {
+        self.surroundings
+            .objects
+            .query_ball(
+                &self.transform.translation.into(),
+                radius,
+                Some(self.entity),
+            )
+            .into_iter()
+            .any(|intersection| **intersection.item() != self.player)
+    }
+
+    /// Returns the nearest friendly repair point and its location.
+    fn haven(&self) -> Option<(Entity, Vec2)> {
+        let position = self.transform.translation.to_flat();
+        self.surroundings
+            .havens
+            .iter()
+            .filter(|(_, &player, _)| player == self.player)
+            .map(|(entity, _, transform)| (entity, transform.translation.to_flat()))
+            .min_by(|a, b| {
+                a.1.distance_squared(position)
+                    .total_cmp(&b.1.distance_squared(position))
+            })
+    }
+
+    /// Returns the path target to be restored once a retreat of the object is
+    /// over.
+    fn resumed_target(&self) -> Option<PathTarget> {
+        if self.surroundings.routed.contains(self.entity) {
+            // Attack-moving units continue to their destination, patrolling
+            // and following units return to their route on their own.
+            self.attack_move.map(|attack_move| {
+                PathTarget::new(attack_move.destination(), PathQueryProps::exact(), false)
+            })
+        } else if self.chasing {
+            self.anchor
+                .map(|anchor| PathTarget::new(anchor, PathQueryProps::exact(), false))
+        } else {
+            self.path.copied()
+        }
+    }
+}
+
+impl<'a, 'w, 's> Agent for EcsAgent<'a, 'w, 's> {
+    fn check(&mut self, condition: Condition) -> bool {
+        match condition {
+            Condition::EnemyInRange(distance) => self.enemy_within(distance),
+            Condition::HealthBelow(threshold) => self
+                .health
+                .map_or(false, |health| health.fraction() < threshold),
+            Condition::Attacking => self.chasing,
+            Condition::AttackMoving => self.attack_move.is_some(),
+            Condition::Idle => {
+                !self.chasing
+                    && !self.retreating
+                    && self.path.is_none()
+                    && self.queue.map_or(true, |queue| queue.is_empty())
+                    && !self.surroundings.routed.contains(self.entity)
+            }
+        }
+    }
+
+    fn act(&mut self, action: Action) -> Status {
+        match action {
+            Action::AttackNearest => {
+                if self.chasing {
+                    return Status::Running;
+                }
+                if self.retreating || !self.armed || (self.attack_move.is_none() && !self.acquires)
+                {
+                    return Status::Failure;
+                }
+
+                self.surroundings
+                    .acquire_events
+                    .send(AcquireTargetEvent::new(self.entity));
+                Status::Running
+            }
+            Action::MoveTo(destination) => {
+                let position = self.transform.translation.to_flat();
+                if position.distance(destination) <= ARRIVAL_DISTANCE {
+                    return Status::Success;
+                }
+                if self
+                    .path
+                    .map_or(false, |path| path.location() == destination)
+                {
+                    return Status::Running;
+                }
+
+                let target = PathTarget::new(destination, PathQueryProps::exact(), false);
+                self.surroundings
+                    .commands
+                    .entity(self.entity)
+                    .insert(target);
+                self.surroundings
+                    .path_events
+                    .send(UpdateEntityPath::new(self.entity, target));
+                Status::Running
+            }
+            Action::Retreat => {
+                if self.retreating {
+                    return Status::Running;
+                }
+                if !self.chasing
+                    && self.path.map_or(false, |path| !path.permanent())
+                    && !self.surroundings.routed.contains(self.entity)
+                {
+                    return Status::Failure;
+                }
+
+                let (haven, location) = match self.haven() {
+                    Some(haven) => haven,
+                    None => return Status::Failure,
+                };
+
+                let retreat = Retreat::new(haven, self.resumed_target());
+                let target = haven_target(location);
+                self.surroundings
+                    .commands
+                    .entity(self.entity)
+                    .remove_bundle::<(ChaseTarget, Leash)>()
+                    .insert(retreat)
+                    .insert(target);
+                self.surroundings
+                    .path_events
+                    .send(UpdateEntityPath::new(self.entity, target));
+
+                self.chasing = false;
+                self.anchor = None;
+                self.retreating = true;
+                Status::Running
+            }
+        }
+    }
+}
+
+struct EvaluationTimer(Timer);
+
+impl Default for EvaluationTimer {
+    fn default() -> Self {
+        Self(Timer::new(EVALUATION_INTERVAL, true))
+    }
+}
+
+fn evaluate(
+    time: Res<Time>,
+    mut timer: Local<EvaluationTimer>,
+    mut surroundings: Surroundings,
+    agents: Agents,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    for (
+        entity,
+        tree,
+        transform,
+        &player,
+        health,
+        armament,
+        stance,
+        movable,
+        chase,
+        leash,
+        path,
+        queue,
+        attack_move,
+        retreat,
+    ) in agents.iter()
+    {
+        let mut agent = EcsAgent {
+            surroundings: &mut surroundings,
+            entity,
+            transform,
+            player,
+            health,
+            armed: armament.is_some(),
+            acquires: stance.map_or(false, |stance| {
+                stance.restricted(movable.is_some()).auto_acquires()
+            }),
+            chasing: chase.is_some(),
+            anchor: leash.map(|leash| leash.anchor()),
+            path,
+            queue,
+            attack_move,
+            retreating: retreat.is_some(),
+        };
+        tree.root().evaluate(&mut agent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use de_core::objects::{BuildingType, UnitType};
+
+    use super::*;
+
+    struct TestAgent {
+        enemy_distance: f32,
+        health: f32,
+        actions: Vec<Action>,
+    }
+
+    impl TestAgent {
+        fn new(enemy_distance: f32, health: f32) -> Self {
+            Self {
+                enemy_distance,
+                health,
+                actions: Vec::new(),
+            }
+        }
+    }
+
+    impl Agent for TestAgent {
+        fn check(&mut self, condition: Condition) -> bool {
+            match condition {
+                Condition::EnemyInRange(distance) => self.enemy_distance <= distance,
+                Condition::HealthBelow(threshold) => self.health < threshold,
+                Condition::Attacking | Condition::AttackMoving => false,
+                Condition::Idle => self.actions.is_empty(),
+            }
+        }
+
+        fn act(&mut self, action: Action) -> Status {
+            self.actions.push(action);
+            match action {
+                Action::AttackNearest | Action::Retreat => Status::Running,
+                Action::MoveTo(_) => Status::Success,
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let retreat = Vec2::new(1., 2.);
+        let tree = Node::Selector(vec![
+            Node::Sequence(vec![
+                Node::Condition(Condition::HealthBelow(0.3)),
+                Node::Action(Action::MoveTo(retreat)),
+            ]),
+            Node::Sequence(vec![
+                Node::Condition(Condition::EnemyInRange(10.)),
+                Node::Action(Action::AttackNearest),
+            ]),
+        ]);
+
+        let mut agent = TestAgent::new(5., 1.);
+        assert_eq!(tree.evaluate(&mut agent), Status::Running);
+        assert_eq!(agent.actions, vec![Action::AttackNearest]);
+
+        let mut agent = TestAgent::new(5., 0.2);
+        assert_eq!(tree.evaluate(&mut agent), Status::Success);
+        assert_eq!(agent.actions, vec![Action::MoveTo(retreat)]);
+
+        let mut agent = TestAgent::new(20., 1.);
+        assert_eq!(tree.evaluate(&mut agent), Status::Failure);
+        assert!(agent.actions.is_empty());
+    }
+
+    #[test]
+    fn test_retreat() {
+        let tree = Node::retreat(0.25);
+
+        let mut agent = TestAgent::new(5., 0.5);
+        assert_eq!(tree.evaluate(&mut agent), Status::Failure);
+        assert!(agent.actions.is_empty());
+
+        let mut agent = TestAgent::new(5., 0.2);
+        assert_eq!(tree.evaluate(&mut agent), Status::Running);
+        assert_eq!(agent.actions, vec![Action::Retreat]);
+    }
+
+    #[test]
+    fn test_default_trees() {
+        let trees = BehaviourTrees::default();
+        assert_eq!(
+            trees
+                .get(ActiveObjectType::Unit(UnitType::Attacker))
+                .unwrap()
+                .root(),
+            &Node::Selector(vec![Node::retreat(RETREAT_THRESHOLD), Node::attack_move()])
+        );
+        assert!(trees
+            .get(ActiveObjectType::Building(BuildingType::Base))
+            .is_none());
+
+        let tree = Node::attack_move();
+        let mut agent = TestAgent::new(5., 1.);
+        assert_eq!(tree.evaluate(&mut agent), Status::Failure);
+        assert!(agent.actions.is_empty());
+    }
+
+    #[test]
+    fn test_invert() {
+        let tree = Node::Sequence(vec![
+            Node::Invert(Box::new(Node::Condition(Condition::EnemyInRange(10.)))),
+            Node::Condition(Condition::Idle),
+        ]);
+        assert_eq!(tree.evaluate(&mut TestAgent::new(5., 1.)), Status::Failure);
+        assert_eq!(tree.evaluate(&mut TestAgent::new(20., 1.)), Status::Success);
+    }
+}
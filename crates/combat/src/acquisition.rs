@@ -0,0 +1,359 @@
+use std::time::Duration;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use de_behaviour::{
+    AcquireTargetEvent, AttackMove, ChaseTarget, Follow, Patrol, Retreat, Stance,
+    MAX_CHASE_DISTANCE,
+};
+use de_core::{
+    objects::{MovableSolid, ObjectType},
+    player::Player,
+    projection::ToFlat,
+    stages::GameStage,
+    state::GameState,
+};
+use de_index::SpatialQuery;
+use de_objects::{Armament, ColliderCache, ObjectCache};
+use de_pathing::PathTarget;
+use iyes_loopless::prelude::*;
+use parry3d::query::Ray;
+
+use crate::{
+    attack::AttackEvent, damage::HitEvent, sightline::LineOfSight, targeting::Targeting,
+    AttackingLabels,
+};
+
+/// Idle armed objects look for enemies this often.
+const SCAN_INTERVAL: Duration = Duration::from_millis(500);
+
+pub(crate) struct AcquisitionPlugin;
+
+impl Plugin for AcquisitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set_to_stage(
+            GameStage::Update,
+            SystemSet::new()
+                .with_system(
+                    scan.run_in_state(GameState::Playing)
+                        .after(AttackingLabels::Explode),
+                )
+                .with_system(
+                    acquire
+                        .run_in_state(GameState::Playing)
+                        .after(AttackingLabels::Explode),
+                )
+                .with_system(
+                    retaliate
+                        .run_in_state(GameState::Playing)
+                        .after(AttackingLabels::Explode),
+                )
+                .with_system(
+                    guard
+                        .run_in_state(GameState::Playing)
+                        .after(AttackingLabels::Explode),
+                ),
+        );
+    }
+}
+
+/// Armed objects which are neither attacking nor retreating and which either
+/// have no orders (are not moving anywhere), are on attack-move or are
+/// patrolling.
+type Idle<'w, 's, Q> = Query<
+    'w,
+    's,
+    Q,
+    (
+        Without<ChaseTarget>,
+        Without<Retreat>,
+        Or<(Without<PathTarget>, With<AttackMove>, With<Patrol>)>,
+    ),
+>;
+
+type Attacker<'a> = (
+    Entity,
+    &'a Transform,
+    &'a ObjectType,
+    &'a Player,
+    &'a Armament,
+    &'a Stance,
+    Option<&'a MovableSolid>,
+    Option<&'a AttackMove>,
+);
+
+#[derive(SystemParam)]
+struct Acquisition<'w, 's> {
+    cache: Res<'w, ObjectCache>,
+    candidates: SpatialQuery<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Transform,
+            &'static ObjectType,
+            &'static Player,
+        ),
+    >,
+    targeting: Targeting<'w, 's>,
+    sightline: LineOfSight<'w, 's>,
+}
+
+impl<'w, 's> Acquisition<'w, 's> {
+    /// Returns an enemy which the attacker should attack on its own or None
+    /// if there is no such enemy or if the attacker may not attack on its own
+    /// due to its stance.
+    fn enemy(&self, attacker: Attacker) -> Option<Entity> {
+        let (attacker, transform, &object_type, &player, armament, stance, movable, attack_move) =
+            attacker;
+
+        let stance = stance.restricted(movable.is_some());
+        // Attack-move is an explicit order to attack, it overrides the
+        // stance.
+        if attack_move.is_none() && !stance.auto_acquires() {
+            return None;
+        }
+
+        let centroid: Vec3 = self.cache.get_collider(object_type).aabb().center().into();
+        let eye = transform.translation + centroid;
+
+        let selector = self
+            .targeting
+            .selector(self.cache.get(object_type).target_priorities(), player);
+        let enemies = self
+            .candidates
+            .query_ball(&eye.into(), armament.max_range(), Some(attacker))
+            .into_iter()
+            .filter(|intersection| {
+                let (_, target_transform, &target_type, &target_player) = *intersection.item();
+                if target_player == player {
+                    return false;
+                }
+
+                let range = match armament.range(target_type) {
+                    Some(range) => range,
+                    None => return false,
+                };
+                if stance.may_chase() {
+                    intersection.distance() <= range
+                } else {
+                    // Objects which may not chase would immediately give up
+                    // targets further than this.
+                    let distance = (target_transform.translation - transform.translation)
+                        .to_flat()
+                        .length();
+                    distance <= MAX_CHASE_DISTANCE * range
+                }
+            })
+            .filter(|intersection| {
+                let (target, target_transform, &target_type, _) = *intersection.item();
+                let target_centroid: Vec3 =
+                    self.cache.get_collider(target_type).aabb().center().into();
+                let to_target = target_transform.translation + target_centroid - eye;
+                match to_target.try_normalize() {
+                    Some(direction) => {
+                        let ray = Ray::new(eye.into(), direction.into());
+                        self.sightline
+                            .sight(&ray, armament.max_range(), attacker)
+                            .entity()
+                            .map_or(false, |e| e == target)
+                    }
+                    None => true,
+                }
+            })
+            .filter_map(|intersection| {
+                let (target, _, &target_type, _) = *intersection.item();
+                self.targeting
+                    .candidate(target, target_type, intersection.distance())
+            });
+
+        selector.select(enemies)
+    }
+}
+
+struct ScanTimer(Timer);
+
+impl Default for ScanTimer {
+    fn default() -> Self {
+        Self(Timer::new(SCAN_INTERVAL, true))
+    }
+}
+
+fn scan(
+    time: Res<Time>,
+    mut timer: Local<ScanTimer>,
+    idle: Idle<Attacker>,
+    acquisition: Acquisition,
+    mut events: EventWriter<AttackEvent>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    for attacker in idle.iter() {
+        let (entity, .., attack_move) = attacker;
+        // Attack-moving objects look for enemies when their behaviour tree
+        // asks for it, see `de_behaviour::Node::attack_move`.
+        if attack_move.is_some() {
+            continue;
+        }
+        if let Some(enemy) = acquisition.enemy(attacker) {
+            events.send(AttackEvent::automatic(entity, enemy));
+        }
+    }
+}
+
+/// Armed objects attack enemies on request of their behaviour tree, see
+/// [`AcquireTargetEvent`].
+fn acquire(
+    mut requests: EventReader<AcquireTargetEvent>,
+    attackers: Query<Attacker, (Without<ChaseTarget>, Without<Retreat>)>,
+    acquisition: Acquisition,
+    mut events: EventWriter<AttackEvent>,
+) {
+    for request in requests.iter() {
+        let attacker = match attackers.get(request.entity()) {
+            Ok(attacker) => attacker,
+            Err(_) => continue,
+        };
+        if let Some(enemy) = acquisition.enemy(attacker) {
+            events.send(AttackEvent::automatic(request.entity(), enemy));
+        }
+    }
+}
+
+/// Idle armed objects attack enemies which damage them.
+fn retaliate(
+    mut hits: EventReader<HitEvent>,
+    idle: Idle<(&Player, &Armament, &Stance, Option<&AttackMove>)>,
+    attackers: Query<(&Player, &ObjectType)>,
+    mut events: EventWriter<AttackEvent>,
+) {
+    for hit in hits.iter() {
+        let attacker = match hit.attacker() {
+            Some(attacker) => attacker,
+            None => continue,
+        };
+        let (player, armament, stance, attack_move) = match idle.get(hit.target()) {
+            Ok(target) => target,
+            Err(_) => continue,
+        };
+        let (attacker_player, &attacker_type) = match attackers.get(attacker) {
+            Ok(attacker) => attacker,
+            Err(_) => continue,
+        };
+
+        if (attack_move.is_some() || stance.auto_acquires())
+            && player != attacker_player
+            && armament.range(attacker_type).is_some()
+        {
+            events.send(AttackEvent::automatic(hit.target(), attacker));
+        }
+    }
+}
+
+/// Guarding objects which are not attacking attack enemies which damage the
+/// guarded entity. Guarding is an explicit order, it overrides the stance.
+fn guard(
+    mut hits: EventReader<HitEvent>,
+    guards: Query<(Entity, &Follow, &Player, &Armament), (Without<ChaseTarget>, Without<Retreat>)>,
+    attackers: Query<(&Player, &ObjectType)>,
+    mut events: EventWriter<AttackEvent>,
+) {
+    for hit in hits.iter() {
+        let attacker = match hit.attacker() {
+            Some(attacker) => attacker,
+            None => continue,
+        };
+        let (&attacker_player, &attacker_type) = match attackers.get(attacker) {
+            Ok(attacker) => attacker,
+            Err(_) => continue,
+        };
+
+        for (entity, follow, &player, armament) in guards.iter() {
+            if defends(
+                follow,
+                player,
+                hit.target(),
+                attacker_player,
+                armament.range(attacker_type).is_some(),
+            ) {
+                events.send(AttackEvent::automatic(entity, attacker));
+            }
+        }
+    }
+}
+
+/// Returns true if a following object should attack the attacker of a hit
+/// entity.
+///
+/// # Arguments
+///
+/// * `follow` - the following of the object.
+///
+/// * `player` - owner of the object.
+///
+/// * `hit` - the damaged entity.
+///
+/// * `attacker_player` - owner of the object which damaged the entity.
+///
+/// * `can_attack` - whether the object is able to attack the attacker.
+fn defends(
+    follow: &Follow,
+    player: Player,
+    hit: Entity,
+    attacker_player: Player,
+    can_attack: bool,
+) -> bool {
+    follow.guards() && follow.entity() == hit && player != attacker_player && can_attack
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defends() {
+        let guarded = Entity::from_raw(1);
+        let guard = Follow::guard(guarded);
+
+        assert!(defends(
+            &guard,
+            Player::Player1,
+            guarded,
+            Player::Player2,
+            true
+        ));
+        // Another entity was hit.
+        assert!(!defends(
+            &guard,
+            Player::Player1,
+            Entity::from_raw(2),
+            Player::Player2,
+            true
+        ));
+        // Friendly fire.
+        assert!(!defends(
+            &guard,
+            Player::Player1,
+            guarded,
+            Player::Player1,
+            true
+        ));
+        // The attacker cannot be attacked by the guard.
+        assert!(!defends(
+            &guard,
+            Player::Player1,
+            guarded,
+            Player::Player2,
+            false
+        ));
+        // Plain following units do not defend the followed entity.
+        assert!(!defends(
+            &Follow::new(guarded),
+            Player::Player1,
+            guarded,
+            Player::Player2,
+            true
+        ));
+    }
+}
@@ -1,22 +1,16 @@
 use std::{cmp::Ordering, collections::BinaryHeap};
 
-use bevy::prelude::*;
-use de_behaviour::ChaseTarget;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use de_behaviour::{AttackMove, ChaseTarget, Leash, Patrol};
 use de_core::{objects::ObjectType, stages::GameStage, state::GameState};
-use de_objects::{ColliderCache, LaserCannon, ObjectCache};
+use de_economy::PowerSupply;
+use de_objects::{Armament, Cannon, ColliderCache, ObjectCache, Turret};
 use iyes_loopless::prelude::*;
 use parry3d::query::Ray;
 
-use crate::laser::LaserFireEvent;
+use crate::{laser::LaserFireEvent, projectile::ProjectileFireEvent};
 use crate::{sightline::LineOfSight, AttackingLabels};
 
-/// Multiple of cannon range. The attacking entities will try to stay as close
-/// or further from attacked targets.
-const MIN_CHASE_DISTNACE: f32 = 0.4;
-/// Multiple of cannon range. The attacking entities will try to stay as close
-/// or closer from attacked targets.
-const MAX_CHASE_DISTNACE: f32 = 0.9;
-
 pub(crate) struct AttackPlugin;
 
 impl Plugin for AttackPlugin {
@@ -47,11 +41,28 @@ impl Plugin for AttackPlugin {
 pub struct AttackEvent {
     attacker: Entity,
     enemy: Entity,
+    explicit: bool,
 }
 
 impl AttackEvent {
+    /// Creates an event of an explicit order to attack an enemy.
     pub fn new(attacker: Entity, enemy: Entity) -> Self {
-        Self { attacker, enemy }
+        Self {
+            attacker,
+            enemy,
+            explicit: true,
+        }
+    }
+
+    /// Creates an event of an attack on an enemy chosen by the attacker on
+    /// its own. The attacker is leashed to its current position, see
+    /// [`Leash`].
+    pub(crate) fn automatic(attacker: Entity, enemy: Entity) -> Self {
+        Self {
+            attacker,
+            enemy,
+            explicit: false,
+        }
     }
 
     fn attacker(&self) -> Entity {
@@ -61,6 +72,10 @@ impl AttackEvent {
     fn enemy(&self) -> Entity {
         self.enemy
     }
+
+    fn explicit(&self) -> bool {
+        self.explicit
+    }
 }
 
 #[derive(Component)]
@@ -69,107 +84,210 @@ struct Attacking;
 fn attack(
     mut commands: Commands,
     mut events: EventReader<AttackEvent>,
-    cannons: Query<&LaserCannon>,
+    armaments: Query<(
+        &Transform,
+        &Armament,
+        Option<&Leash>,
+        Option<&AttackMove>,
+        Option<&Patrol>,
+    )>,
+    targets: Query<&ObjectType>,
 ) {
     for event in events.iter() {
-        if let Ok(cannon) = cannons.get(event.attacker()) {
-            commands.entity(event.attacker()).insert(ChaseTarget::new(
-                event.enemy(),
-                MIN_CHASE_DISTNACE * cannon.range(),
-                MAX_CHASE_DISTNACE * cannon.range(),
-            ));
+        let (transform, armament, leash, attack_move, patrol) =
+            match armaments.get(event.attacker()) {
+                Ok(attacker) => attacker,
+                Err(_) => continue,
+            };
+        let target_type = match targets.get(event.enemy()) {
+            Ok(&object_type) => object_type,
+            Err(_) => continue,
+        };
+
+        // Objects which none of the cannons is able to attack are ignored.
+        if let Some(range) = armament.range(target_type) {
+            let mut entity_commands = commands.entity(event.attacker());
+            entity_commands.insert(ChaseTarget::within_range(event.enemy(), range));
+
+            if event.explicit() {
+                if leash.is_some() {
+                    entity_commands.remove::<Leash>();
+                }
+                if attack_move.is_some() {
+                    entity_commands.remove::<AttackMove>();
+                }
+                if patrol.is_some() {
+                    entity_commands.remove::<Patrol>();
+                }
+            } else if leash.is_none() {
+                entity_commands.insert(Leash::from(transform));
+            }
         }
     }
 }
 
-fn update(time: Res<Time>, mut cannons: Query<&mut LaserCannon, With<Attacking>>) {
-    for mut cannon in cannons.iter_mut() {
-        cannon.timer_mut().tick(time.delta());
+/// Charges cannons of attacking objects. Cannons of objects which consume
+/// energy charge proportionally slower when the objects are not fully
+/// powered.
+fn update(
+    time: Res<Time>,
+    mut armaments: Query<(&mut Armament, Option<&PowerSupply>), With<Attacking>>,
+) {
+    for (mut armament, supply) in armaments.iter_mut() {
+        let tick = match supply {
+            Some(supply) => time.delta().mul_f32(supply.efficiency()),
+            None => time.delta(),
+        };
+        for cannon in armament.cannons_mut() {
+            cannon.timer_mut().tick(tick);
+        }
     }
 }
 
 fn aim_and_fire(
     mut commands: Commands,
+    time: Res<Time>,
     cache: Res<ObjectCache>,
     mut attackers: Query<(
         Entity,
         &Transform,
-        &mut LaserCannon,
+        &mut Armament,
         &ChaseTarget,
+        Option<&mut Turret>,
         Option<&Attacking>,
     )>,
     targets: Query<(&Transform, &ObjectType)>,
     sightline: LineOfSight,
-    mut events: EventWriter<LaserFireEvent>,
+    mut fire_events: FireEvents,
 ) {
     let attackers = attackers.iter_mut();
     // The queue is used so that attacking has the same result as if it was
     // done in real-time (unaffected by update frequency).
     let mut fire_queue = BinaryHeap::new();
 
-    for (attacker, attacker_transform, mut cannon, target, marker) in attackers {
-        let target_position = match targets.get(target.entity()) {
+    for (attacker, attacker_transform, armament, target, turret, marker) in attackers {
+        let (target_position, target_type) = match targets.get(target.entity()) {
             Ok((transform, &object_type)) => {
                 let centroid: Vec3 = cache.get_collider(object_type).aabb().center().into();
-                transform.translation + centroid
+                (transform.translation + centroid, object_type)
             }
             Err(_) => continue,
         };
 
-        let muzzle = attacker_transform.translation + cannon.muzzle();
-        let to_target = (target_position - muzzle)
-            .try_normalize()
-            .expect("Attacker and target to close together");
-        let ray = Ray::new(muzzle.into(), to_target.into());
-        let aims_at_target = sightline
-            .sight(&ray, cannon.range(), attacker)
-            .entity()
-            .map_or(true, |e| e != target.entity());
-
-        if aims_at_target {
-            if marker.is_some() {
-                cannon.timer_mut().reset();
-                commands.entity(attacker).remove::<Attacking>();
+        // Cannons are mounted on the turret (if any), therefore they may
+        // fire only once the turret faces the target.
+        let (rotation, aligned) = match turret {
+            Some(mut turret) => {
+                let local = attacker_transform.rotation.inverse()
+                    * (target_position - attacker_transform.translation);
+                let heading = Turret::heading_of(local);
+                // Access the component mutably only when really needed for
+                // optimal Bevy change detection.
+                let aligned = turret.faces(heading) || turret.turn(heading, time.delta_seconds());
+                (attacker_transform.rotation * turret.rotation(), aligned)
             }
-        } else {
-            if marker.is_none() {
-                commands.entity(attacker).insert(Attacking);
+            None => (attacker_transform.rotation, true),
+        };
+
+        let mut attacking = false;
+        for cannon in armament.into_inner().cannons_mut() {
+            if !aligned || !cannon.targets().matches(target_type) {
+                cannon.timer_mut().reset();
+                continue;
             }
-            if cannon.timer_mut().check_and_update() {
-                fire_queue.push(FireScheduleItem::new(attacker, ray, cannon.into_inner()));
+
+            let muzzle = attacker_transform.translation + rotation * cannon.muzzle();
+            let to_target = (target_position - muzzle)
+                .try_normalize()
+                .expect("Attacker and target to close together");
+            let ray = Ray::new(muzzle.into(), to_target.into());
+            let aims_at_target = sightline
+                .sight(&ray, cannon.range(), attacker)
+                .entity()
+                .map_or(false, |e| e == target.entity());
+
+            if aims_at_target {
+                attacking = true;
+                if cannon.timer_mut().check_and_update() {
+                    fire_queue.push(FireScheduleItem::new(
+                        attacker,
+                        target.entity(),
+                        target_position,
+                        ray,
+                        cannon,
+                    ));
+                }
+            } else {
+                // Each cannon has to (re)charge after it gets its aim back.
+                cannon.timer_mut().reset();
             }
         }
+
+        if attacking && marker.is_none() {
+            commands.entity(attacker).insert(Attacking);
+        } else if !attacking && marker.is_some() {
+            commands.entity(attacker).remove::<Attacking>();
+        }
     }
 
     while let Some(mut fire_schedule_item) = fire_queue.pop() {
-        if fire_schedule_item.fire(&mut events) {
+        if fire_schedule_item.fire(&mut fire_events) {
             fire_queue.push(fire_schedule_item);
         }
     }
 }
 
+#[derive(SystemParam)]
+struct FireEvents<'w, 's> {
+    lasers: EventWriter<'w, 's, LaserFireEvent>,
+    projectiles: EventWriter<'w, 's, ProjectileFireEvent>,
+}
+
 struct FireScheduleItem<'a> {
     attacker: Entity,
+    target: Entity,
+    target_position: Vec3,
     ray: Ray,
-    cannon: &'a mut LaserCannon,
+    cannon: &'a mut Cannon,
 }
 
 impl<'a> FireScheduleItem<'a> {
-    fn new(attacker: Entity, ray: Ray, cannon: &'a mut LaserCannon) -> Self {
+    fn new(
+        attacker: Entity,
+        target: Entity,
+        target_position: Vec3,
+        ray: Ray,
+        cannon: &'a mut Cannon,
+    ) -> Self {
         Self {
             attacker,
+            target,
+            target_position,
             ray,
             cannon,
         }
     }
 
-    fn fire(&mut self, events: &mut EventWriter<LaserFireEvent>) -> bool {
-        events.send(LaserFireEvent::new(
-            self.attacker,
-            self.ray,
-            self.cannon.range(),
-            self.cannon.damage(),
-        ));
+    fn fire(&mut self, events: &mut FireEvents) -> bool {
+        match self.cannon.projectile() {
+            Some(projectile) => events.projectiles.send(ProjectileFireEvent::new(
+                self.attacker,
+                self.target,
+                self.ray.origin.into(),
+                self.target_position,
+                projectile,
+                self.cannon.range(),
+                self.cannon.damage(),
+                self.cannon.damage_type(),
+            )),
+            None => events.lasers.send(LaserFireEvent::new(
+                self.attacker,
+                self.ray,
+                self.cannon.range(),
+                self.cannon.damage(),
+                self.cannon.damage_type(),
+            )),
+        }
         self.cannon.timer_mut().check_and_update()
     }
 }
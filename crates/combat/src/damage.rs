@@ -0,0 +1,113 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use de_core::objects::ObjectType;
+use de_objects::{DamageMultipliers, DamageType, Health, ObjectCache};
+use de_signs::UpdateBarValueEvent;
+
+pub(crate) struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<HitEvent>();
+    }
+}
+
+/// This event is sent whenever an entity is damaged.
+pub struct HitEvent {
+    attacker: Option<Entity>,
+    target: Entity,
+    damage: f32,
+}
+
+impl HitEvent {
+    fn new(attacker: Option<Entity>, target: Entity, damage: f32) -> Self {
+        Self {
+            attacker,
+            target,
+            damage,
+        }
+    }
+
+    /// The entity which caused the damage. It is None if the damage was not
+    /// caused by an entity (e.g. by an explosion of a destroyed object).
+    pub fn attacker(&self) -> Option<Entity> {
+        self.attacker
+    }
+
+    /// The damaged entity.
+    pub fn target(&self) -> Entity {
+        self.target
+    }
+
+    /// Damage dealt to the target, i.e. damage after application of armor
+    /// based multipliers.
+    pub fn damage(&self) -> f32 {
+        self.damage
+    }
+}
+
+/// System parameter used to deal damage to and to heal objects. Damage is
+/// adjusted by [`DamageMultipliers`] based on armor class of the damaged
+/// objects.
+#[derive(SystemParam)]
+pub(crate) struct Susceptible<'w, 's> {
+    cache: Res<'w, ObjectCache>,
+    multipliers: Res<'w, DamageMultipliers>,
+    entities: Query<'w, 's, (&'static ObjectType, &'static mut Health)>,
+    bar: EventWriter<'w, 's, UpdateBarValueEvent>,
+    hits: EventWriter<'w, 's, HitEvent>,
+}
+
+impl<'w, 's> Susceptible<'w, 's> {
+    /// Returns health of an entity or None if the entity does not exist or
+    /// cannot be damaged.
+    pub(crate) fn health(&self, entity: Entity) -> Option<&Health> {
+        self.entities.get(entity).ok().map(|(_, health)| health)
+    }
+
+    /// Lowers health of an entity. Nothing happens (e.g. no [`HitEvent`] is
+    /// sent) if the entity does not exist, cannot be damaged or is already
+    /// destroyed.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - the damaged entity.
+    ///
+    /// * `attacker` - the entity which caused the damage, if any.
+    ///
+    /// * `damage` - raw damage before application of armor based multipliers.
+    ///
+    /// * `damage_type` - type of the dealt damage.
+    pub(crate) fn hit(
+        &mut self,
+        entity: Entity,
+        attacker: Option<Entity>,
+        damage: f32,
+        damage_type: DamageType,
+    ) {
+        if let Ok((&object_type, mut health)) = self.entities.get_mut(entity) {
+            // Destroyed entities might be hit again before they are despawned.
+            if health.destroyed() {
+                return;
+            }
+
+            let armor = self.cache.get(object_type).armor();
+            let damage = self.multipliers.apply(damage, damage_type, armor);
+            health.hit(damage, attacker);
+            self.hits.send(HitEvent::new(attacker, entity, damage));
+            self.bar
+                .send(UpdateBarValueEvent::new(entity, health.fraction()));
+        }
+    }
+
+    /// Increases health of an entity. Nothing happens if the entity does not
+    /// exist, is destroyed or is not damaged.
+    pub(crate) fn heal(&mut self, entity: Entity, amount: f32) {
+        if let Ok((_, mut health)) = self.entities.get_mut(entity) {
+            if health.damaged() && !health.destroyed() {
+                health.heal(amount);
+                self.bar
+                    .send(UpdateBarValueEvent::new(entity, health.fraction()));
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+use de_core::{objects::ObjectType, player::Player, stages::GameStage, state::GameState};
+use de_index::SpatialQuery;
+use de_objects::{ColliderCache, DamageType, Explosion, FriendlyFire, Health, ObjectCache};
+use de_spawner::SpawnerLabels;
+use iyes_loopless::prelude::*;
+
+use crate::{damage::Susceptible, AttackingLabels};
+
+pub(crate) struct ExplosionPlugin;
+
+impl Plugin for ExplosionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ExplosionEvent>().add_system_set_to_stage(
+            GameStage::Update,
+            SystemSet::new()
+                .with_system(
+                    explode
+                        .run_in_state(GameState::Playing)
+                        .label(AttackingLabels::Explode)
+                        .after(AttackingLabels::Fire)
+                        .before(SpawnerLabels::Destroyer),
+                )
+                .with_system(
+                    detonate
+                        .run_in_state(GameState::Playing)
+                        .after(AttackingLabels::Explode)
+                        .before(SpawnerLabels::Destroyer),
+                ),
+        );
+    }
+}
+
+/// Send this event to damage all objects within a radius from a point. The
+/// explosion deals [`DamageType::Explosive`] damage.
+pub(crate) struct ExplosionEvent {
+    attacker: Option<Entity>,
+    center: Vec3,
+    player: Option<Player>,
+    explosion: Explosion,
+}
+
+impl ExplosionEvent {
+    /// Creates a new explosion event.
+    ///
+    /// # Arguments
+    ///
+    /// * `attacker` - the entity which caused the explosion, if any.
+    ///
+    /// * `center` - point of the explosion.
+    ///
+    /// * `player` - the player who caused the explosion. Objects of this
+    ///   player are not damaged if the explosion disallows friendly fire.
+    ///
+    /// * `explosion` - properties of the explosion.
+    pub(crate) fn new(
+        attacker: Option<Entity>,
+        center: Vec3,
+        player: Option<Player>,
+        explosion: Explosion,
+    ) -> Self {
+        Self {
+            attacker,
+            center,
+            player,
+            explosion,
+        }
+    }
+
+    fn attacker(&self) -> Option<Entity> {
+        self.attacker
+    }
+
+    fn center(&self) -> Vec3 {
+        self.center
+    }
+
+    fn player(&self) -> Option<Player> {
+        self.player
+    }
+
+    fn explosion(&self) -> &Explosion {
+        &self.explosion
+    }
+}
+
+fn explode(
+    mut events: EventReader<ExplosionEvent>,
+    entities: SpatialQuery<(Entity, Option<&Player>), With<Health>>,
+    mut susceptible: Susceptible,
+) {
+    for event in events.iter() {
+        let explosion = event.explosion();
+        let intersections = entities.query_ball(&event.center().into(), explosion.radius(), None);
+
+        for intersection in intersections {
+            let (entity, player) = *intersection.item();
+            if let FriendlyFire::Deny = explosion.friendly_fire() {
+                if event.player().is_some() && player.copied() == event.player() {
+                    continue;
+                }
+            }
+
+            let damage = explosion.damage_at(intersection.distance());
+            if damage <= 0. {
+                continue;
+            }
+
+            susceptible.hit(entity, event.attacker(), damage, DamageType::Explosive);
+        }
+    }
+}
+
+/// Sends explosion events for objects which were destroyed during this frame
+/// and explode on destruction.
+fn detonate(
+    cache: Res<ObjectCache>,
+    entities: Query<(&ObjectType, &Transform, &Health, Option<&Player>), Changed<Health>>,
+    mut events: EventWriter<ExplosionEvent>,
+) {
+    for (&object_type, transform, health, player) in entities.iter() {
+        if !health.destroyed() {
+            continue;
+        }
+
+        if let Some(explosion) = cache.get(object_type).explosion() {
+            let centroid: Vec3 = cache.get_collider(object_type).aabb().center().into();
+            events.send(ExplosionEvent::new(
+                None,
+                transform.translation + centroid,
+                player.copied(),
+                explosion,
+            ));
+        }
+    }
+}
@@ -1,12 +1,11 @@
 use bevy::prelude::*;
 use de_core::{stages::GameStage, state::GameState};
-use de_objects::Health;
-use de_signs::UpdateBarValueEvent;
+use de_objects::DamageType;
 use de_spawner::SpawnerLabels;
 use iyes_loopless::prelude::*;
 use parry3d::query::Ray;
 
-use crate::{sightline::LineOfSight, AttackingLabels};
+use crate::{damage::Susceptible, sightline::LineOfSight, stats::ShotEvent, AttackingLabels};
 
 pub(crate) struct LaserPlugin;
 
@@ -30,6 +29,7 @@ pub(crate) struct LaserFireEvent {
     ray: Ray,
     max_toi: f32,
     damage: f32,
+    damage_type: DamageType,
 }
 
 impl LaserFireEvent {
@@ -45,14 +45,22 @@ impl LaserFireEvent {
     ///   point is given by formula `ray.origin + max_toi * ray.dir`.
     ///
     /// * `damage` - if an entity is hit, its health will be lowered by this
-    ///   amount.
-    #[allow(dead_code)]
-    pub(crate) fn new(attacker: Entity, ray: Ray, max_toi: f32, damage: f32) -> Self {
+    ///   amount (adjusted by the entity armor).
+    ///
+    /// * `damage_type` - type of the damage dealt by the laser.
+    pub(crate) fn new(
+        attacker: Entity,
+        ray: Ray,
+        max_toi: f32,
+        damage: f32,
+        damage_type: DamageType,
+    ) -> Self {
         Self {
             attacker,
             ray,
             max_toi,
             damage,
+            damage_type,
         }
     }
 
@@ -71,27 +79,39 @@ impl LaserFireEvent {
     fn damage(&self) -> f32 {
         self.damage
     }
+
+    fn damage_type(&self) -> DamageType {
+        self.damage_type
+    }
 }
 
 fn fire(
     mut fires: EventReader<LaserFireEvent>,
     sightline: LineOfSight,
-    mut susceptible: Query<&mut Health>,
-    mut bar: EventWriter<UpdateBarValueEvent>,
+    mut susceptible: Susceptible,
+    mut shots: EventWriter<ShotEvent>,
 ) {
     for fire in fires.iter() {
         if susceptible
-            .get(fire.attacker())
+            .health(fire.attacker())
             .map_or(true, |health| health.destroyed())
         {
             continue;
         }
 
         let observation = sightline.sight(fire.ray(), fire.max_toi(), fire.attacker());
-        if let Some(entity) = observation.entity() {
-            let mut health = susceptible.get_mut(entity).unwrap();
-            health.hit(fire.damage());
-            bar.send(UpdateBarValueEvent::new(entity, health.fraction()));
+        let target = observation
+            .entity()
+            .filter(|&entity| susceptible.health(entity).is_some());
+        shots.send(ShotEvent::new(fire.attacker(), target.is_some()));
+
+        if let Some(entity) = target {
+            susceptible.hit(
+                entity,
+                Some(fire.attacker()),
+                fire.damage(),
+                fire.damage_type(),
+            );
         }
     }
 }
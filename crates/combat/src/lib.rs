@@ -1,20 +1,51 @@
+use acquisition::AcquisitionPlugin;
 pub use attack::AttackEvent;
 use attack::AttackPlugin;
 use bevy::{
     app::PluginGroupBuilder,
     prelude::{PluginGroup, SystemLabel},
 };
+use damage::DamagePlugin;
+pub use damage::HitEvent;
+use explosion::ExplosionPlugin;
 use laser::LaserPlugin;
+use projectile::ProjectilePlugin;
+use repair::RepairPlugin;
+use stats::StatsPlugin;
+pub use stats::{
+    CombatStats, DamageRecord, KillRecord, ObjectStats, ParticipantRecord, PlayerStats, StatsExport,
+};
+use targeting::TargetingPlugin;
+pub use targeting::{FocusEvent, FocusTargets};
+use turret::TurretPlugin;
 
+mod acquisition;
 mod attack;
+mod damage;
+mod explosion;
 mod laser;
+mod projectile;
+mod repair;
 mod sightline;
+mod stats;
+mod targeting;
+mod turret;
 
 pub struct CombatPluginGroup;
 
 impl PluginGroup for CombatPluginGroup {
     fn build(&mut self, group: &mut PluginGroupBuilder) {
-        group.add(LaserPlugin).add(AttackPlugin);
+        group
+            .add(DamagePlugin)
+            .add(LaserPlugin)
+            .add(ProjectilePlugin)
+            .add(ExplosionPlugin)
+            .add(AttackPlugin)
+            .add(RepairPlugin)
+            .add(AcquisitionPlugin)
+            .add(StatsPlugin)
+            .add(TurretPlugin)
+            .add(TargetingPlugin);
     }
 }
 
@@ -22,4 +53,5 @@ impl PluginGroup for CombatPluginGroup {
 enum AttackingLabels {
     Update,
     Fire,
+    Explode,
 }
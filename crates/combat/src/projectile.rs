@@ -0,0 +1,502 @@
+use bevy::prelude::*;
+use de_core::{objects::ObjectType, player::Player, stages::GameStage, state::GameState};
+use de_objects::{ColliderCache, DamageType, Explosion, Guidance, Health, ObjectCache, Projectile};
+use de_spawner::SpawnerLabels;
+use iyes_loopless::prelude::*;
+use parry3d::query::Ray;
+
+use crate::{
+    damage::Susceptible, explosion::ExplosionEvent, sightline::LineOfSight, stats::ShotEvent,
+    AttackingLabels,
+};
+
+/// Gravitational acceleration in meters per second squared. It affects only
+/// ballistic projectiles.
+const GRAVITY: f32 = 9.81;
+/// Maximum angular speed (in radians per second) of homing projectiles.
+const HOMING_TURN_RATE: f32 = 2.;
+/// Multiple of the time it takes a projectile to fly cannon range distance.
+/// Projectiles which fly for longer time are removed from the game.
+const MAX_FLIGHT_TIME: f32 = 2.;
+const PROJECTILE_RADIUS: f32 = 0.15;
+
+pub(crate) struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ProjectileFireEvent>()
+            .add_event::<ImpactEvent>()
+            .add_enter_system(GameState::Loading, setup)
+            .add_exit_system(GameState::Playing, cleanup)
+            .add_system_to_stage(GameStage::Movement, fly.run_in_state(GameState::Playing))
+            .add_system_set_to_stage(
+                GameStage::Update,
+                SystemSet::new()
+                    .with_system(
+                        fire.run_in_state(GameState::Playing)
+                            .label(AttackingLabels::Fire)
+                            .before(SpawnerLabels::Destroyer),
+                    )
+                    .with_system(
+                        impact
+                            .run_in_state(GameState::Playing)
+                            .label(AttackingLabels::Fire)
+                            .before(SpawnerLabels::Destroyer),
+                    ),
+            );
+    }
+}
+
+/// Send this event to fire a projectile from an entity towards a target.
+///
+/// This event is ignored when the attacker has 0 health or no longer exists.
+pub(crate) struct ProjectileFireEvent {
+    attacker: Entity,
+    target: Entity,
+    muzzle: Vec3,
+    aim: Vec3,
+    projectile: Projectile,
+    range: f32,
+    damage: f32,
+    damage_type: DamageType,
+}
+
+impl ProjectileFireEvent {
+    /// Creates a new projectile fire event.
+    ///
+    /// # Arguments
+    ///
+    /// * `attacker` - the firing entity.
+    ///
+    /// * `target` - the targeted entity. Homing projectiles follow this
+    ///   entity.
+    ///
+    /// * `muzzle` - position where the projectile is spawned.
+    ///
+    /// * `aim` - the point the projectile is aimed at.
+    ///
+    /// * `projectile` - properties of the fired projectile.
+    ///
+    /// * `range` - range of the firing cannon. It limits maximum flight time
+    ///   of the projectile.
+    ///
+    /// * `damage` - if an entity is hit, its health will be lowered by this
+    ///   amount (adjusted by the entity armor). Explosive projectiles deal
+    ///   only the explosion damage, thus this is ignored for them.
+    ///
+    /// * `damage_type` - type of the damage dealt by the projectile on a
+    ///   direct hit.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        attacker: Entity,
+        target: Entity,
+        muzzle: Vec3,
+        aim: Vec3,
+        projectile: Projectile,
+        range: f32,
+        damage: f32,
+        damage_type: DamageType,
+    ) -> Self {
+        Self {
+            attacker,
+            target,
+            muzzle,
+            aim,
+            projectile,
+            range,
+            damage,
+            damage_type,
+        }
+    }
+
+    fn attacker(&self) -> Entity {
+        self.attacker
+    }
+
+    fn target(&self) -> Entity {
+        self.target
+    }
+
+    fn muzzle(&self) -> Vec3 {
+        self.muzzle
+    }
+
+    fn aim(&self) -> Vec3 {
+        self.aim
+    }
+
+    fn projectile(&self) -> Projectile {
+        self.projectile
+    }
+
+    fn range(&self) -> f32 {
+        self.range
+    }
+
+    fn damage(&self) -> f32 {
+        self.damage
+    }
+
+    fn damage_type(&self) -> DamageType {
+        self.damage_type
+    }
+
+    /// Returns initial velocity of the projectile.
+    fn velocity(&self) -> Vec3 {
+        initial_velocity(
+            self.aim() - self.muzzle(),
+            self.projectile.speed(),
+            self.projectile.guidance(),
+        )
+    }
+
+    /// Returns maximum flight time of the projectile in seconds.
+    fn flight_time(&self) -> f32 {
+        let speed = self.projectile.speed();
+        if speed <= f32::EPSILON {
+            0.
+        } else {
+            MAX_FLIGHT_TIME * self.range() / speed
+        }
+    }
+}
+
+/// An in-flight projectile.
+#[derive(Component)]
+struct Shell {
+    attacker: Entity,
+    /// Owner of the attacker.
+    player: Option<Player>,
+    target: Entity,
+    guidance: Guidance,
+    velocity: Vec3,
+    damage: f32,
+    damage_type: DamageType,
+    explosion: Option<Explosion>,
+    /// Remaining flight time in seconds.
+    remaining: f32,
+}
+
+impl Shell {
+    fn from_event(event: &ProjectileFireEvent, player: Option<Player>) -> Self {
+        Self {
+            attacker: event.attacker(),
+            player,
+            target: event.target(),
+            guidance: event.projectile().guidance(),
+            velocity: event.velocity(),
+            damage: event.damage(),
+            damage_type: event.damage_type(),
+            explosion: event.projectile().explosion(),
+            remaining: event.flight_time(),
+        }
+    }
+}
+
+/// This event is sent when a projectile hits an obstacle or when it runs out
+/// of flight time.
+struct ImpactEvent {
+    shell: Entity,
+    attacker: Entity,
+    entity: Option<Entity>,
+    damage: f32,
+    damage_type: DamageType,
+    explosive: bool,
+}
+
+impl ImpactEvent {
+    fn new(shell: Entity, entity: Option<Entity>, source: &Shell) -> Self {
+        Self {
+            shell,
+            attacker: source.attacker,
+            entity,
+            damage: source.damage,
+            damage_type: source.damage_type,
+            explosive: source.explosion.is_some(),
+        }
+    }
+
+    fn shell(&self) -> Entity {
+        self.shell
+    }
+
+    fn attacker(&self) -> Entity {
+        self.attacker
+    }
+
+    /// The hit entity, if any.
+    fn entity(&self) -> Option<Entity> {
+        self.entity
+    }
+
+    fn damage(&self) -> f32 {
+        self.damage
+    }
+
+    fn damage_type(&self) -> DamageType {
+        self.damage_type
+    }
+
+    /// Returns true if the projectile explodes on impact. Explosive
+    /// projectiles do not deal direct hit damage, the hit entity is damaged
+    /// by the explosion instead.
+    fn explosive(&self) -> bool {
+        self.explosive
+    }
+}
+
+struct ProjectileAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+impl ProjectileAssets {
+    fn mesh(&self) -> Handle<Mesh> {
+        self.mesh.clone()
+    }
+
+    fn material(&self) -> Handle<StandardMaterial> {
+        self.material.clone()
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(ProjectileAssets {
+        mesh: meshes.add(Mesh::from(shape::UVSphere {
+            radius: PROJECTILE_RADIUS,
+            sectors: 8,
+            stacks: 6,
+        })),
+        material: materials.add(StandardMaterial {
+            base_color: Color::ORANGE,
+            emissive: Color::ORANGE_RED,
+            unlit: true,
+            ..Default::default()
+        }),
+    });
+}
+
+fn fire(
+    mut commands: Commands,
+    assets: Res<ProjectileAssets>,
+    mut fires: EventReader<ProjectileFireEvent>,
+    attackers: Query<(&Health, Option<&Player>)>,
+) {
+    for fire in fires.iter() {
+        let player = match attackers.get(fire.attacker()) {
+            Ok((health, player)) if !health.destroyed() => player.copied(),
+            _ => continue,
+        };
+
+        commands
+            .spawn_bundle(PbrBundle {
+                mesh: assets.mesh(),
+                material: assets.material(),
+                transform: Transform::from_translation(fire.muzzle()),
+                ..Default::default()
+            })
+            .insert(Shell::from_event(fire, player));
+    }
+}
+
+fn cleanup(mut commands: Commands, shells: Query<Entity, With<Shell>>) {
+    for entity in shells.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn fly(
+    time: Res<Time>,
+    cache: Res<ObjectCache>,
+    mut shells: Query<(Entity, &mut Shell, &mut Transform)>,
+    targets: Query<(&Transform, &ObjectType), Without<Shell>>,
+    sightline: LineOfSight,
+    mut impacts: EventWriter<ImpactEvent>,
+    mut explosions: EventWriter<ExplosionEvent>,
+) {
+    let time_delta = time.delta_seconds();
+
+    for (entity, mut shell, mut transform) in shells.iter_mut() {
+        match shell.guidance {
+            Guidance::Straight => (),
+            Guidance::Ballistic => shell.velocity -= time_delta * GRAVITY * Vec3::Y,
+            Guidance::Homing => {
+                if let Ok((target_transform, &object_type)) = targets.get(shell.target) {
+                    let centroid: Vec3 = cache.get_collider(object_type).aabb().center().into();
+                    let to_target = target_transform.translation + centroid - transform.translation;
+                    shell.velocity =
+                        steer(shell.velocity, to_target, HOMING_TURN_RATE * time_delta);
+                }
+            }
+        }
+
+        let displacement = time_delta * shell.velocity;
+        let distance = displacement.length();
+        // Shells which do not move (e.g. a homing shell right above its
+        // target) cannot hit anything but they still expire.
+        if distance > f32::EPSILON {
+            let ray = Ray::new(
+                transform.translation.into(),
+                (displacement / distance).into(),
+            );
+            let observation = sightline.sight(&ray, distance, shell.attacker);
+            if observation.toi() < distance || observation.entity().is_some() {
+                transform.translation += observation.toi() * Vec3::from(ray.dir);
+                impacts.send(ImpactEvent::new(entity, observation.entity(), &shell));
+                if let Some(explosion) = shell.explosion {
+                    explosions.send(ExplosionEvent::new(
+                        Some(shell.attacker),
+                        transform.translation,
+                        shell.player,
+                        explosion,
+                    ));
+                }
+                continue;
+            }
+
+            transform.translation += displacement;
+        }
+
+        shell.remaining -= time_delta;
+        if shell.remaining <= 0. {
+            impacts.send(ImpactEvent::new(entity, None, &shell));
+        }
+    }
+}
+
+fn impact(
+    mut commands: Commands,
+    mut impacts: EventReader<ImpactEvent>,
+    mut susceptible: Susceptible,
+    mut shots: EventWriter<ShotEvent>,
+) {
+    for impact in impacts.iter() {
+        commands.entity(impact.shell()).despawn_recursive();
+
+        let target = impact
+            .entity()
+            .filter(|&entity| susceptible.health(entity).is_some());
+        shots.send(ShotEvent::new(impact.attacker(), target.is_some()));
+
+        if impact.explosive() {
+            continue;
+        }
+        if let Some(entity) = target {
+            susceptible.hit(
+                entity,
+                Some(impact.attacker()),
+                impact.damage(),
+                impact.damage_type(),
+            );
+        }
+    }
+}
+
+/// Returns initial velocity of a projectile.
+///
+/// # Arguments
+///
+/// * `to_aim` - vector from the muzzle to the aimed point.
+///
+/// * `speed` - speed of the projectile, see [`Projectile::speed`].
+///
+/// * `guidance` - guidance of the projectile.
+fn initial_velocity(to_aim: Vec3, speed: f32, guidance: Guidance) -> Vec3 {
+    if let Guidance::Ballistic = guidance {
+        let horizontal = Vec3::new(to_aim.x, 0., to_aim.z);
+        let distance = horizontal.length();
+        if distance > f32::EPSILON && speed > f32::EPSILON {
+            // Horizontal speed is constant, vertical speed is chosen so
+            // that the projectile lands at the aimed point.
+            let flight_time = distance / speed;
+            let vertical = to_aim.y / flight_time + 0.5 * GRAVITY * flight_time;
+            return speed * (horizontal / distance) + vertical * Vec3::Y;
+        }
+    }
+
+    speed * to_aim.normalize_or_zero()
+}
+
+/// Returns velocity of a homing projectile turned towards its target by at
+/// most `max_angle` radians. The speed of the projectile is retained.
+fn steer(velocity: Vec3, to_target: Vec3, max_angle: f32) -> Vec3 {
+    let (current, desired) = match (velocity.try_normalize(), to_target.try_normalize()) {
+        (Some(current), Some(desired)) => (current, desired),
+        _ => return velocity,
+    };
+
+    let angle = current.angle_between(desired);
+    if angle <= f32::EPSILON {
+        return velocity;
+    }
+
+    let fraction = (max_angle / angle).min(1.);
+    let direction = current
+        .lerp(desired, fraction)
+        .try_normalize()
+        .unwrap_or(current);
+    velocity.length() * direction
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn test_initial_velocity() {
+        let to_aim = Vec3::new(3., 0., 4.);
+        let expected = Vec3::new(6., 0., 8.);
+        assert!(initial_velocity(to_aim, 10., Guidance::Straight).abs_diff_eq(expected, 1e-5));
+        assert!(initial_velocity(to_aim, 10., Guidance::Homing).abs_diff_eq(expected, 1e-5));
+        assert_eq!(
+            initial_velocity(Vec3::ZERO, 10., Guidance::Straight),
+            Vec3::ZERO
+        );
+        assert_eq!(
+            initial_velocity(to_aim, 0., Guidance::Ballistic),
+            Vec3::ZERO
+        );
+    }
+
+    #[test]
+    fn test_ballistic_trajectory() {
+        let to_aim = Vec3::new(30., -2., 40.);
+        let velocity = initial_velocity(to_aim, 10., Guidance::Ballistic);
+        assert!(Vec3::new(velocity.x, 0., velocity.z).abs_diff_eq(Vec3::new(6., 0., 8.), 1e-5));
+
+        // The projectile flies 50 meters horizontally in 5 seconds and it has
+        // to land at the aimed point.
+        let flight_time: f32 = 5.;
+        let landing = velocity * flight_time - 0.5 * GRAVITY * flight_time.powi(2) * Vec3::Y;
+        assert!(landing.abs_diff_eq(to_aim, 1e-3));
+
+        // Projectiles fired straight up fly straight.
+        assert!(
+            initial_velocity(Vec3::new(0., 5., 0.), 10., Guidance::Ballistic)
+                .abs_diff_eq(Vec3::new(0., 10., 0.), 1e-5)
+        );
+    }
+
+    #[test]
+    fn test_steer() {
+        let velocity = Vec3::new(10., 0., 0.);
+
+        assert_eq!(steer(velocity, Vec3::new(5., 0., 0.), 0.1), velocity);
+        assert_eq!(steer(velocity, Vec3::ZERO, 0.1), velocity);
+        assert_eq!(steer(Vec3::ZERO, Vec3::X, 0.1), Vec3::ZERO);
+
+        let steered = steer(velocity, Vec3::new(0., 0., 5.), 0.1);
+        assert!((steered.length() - 10.).abs() < 1e-4);
+        assert!(steered.z > 0.);
+        assert!(steered.angle_between(velocity) <= 0.1 + 1e-4);
+
+        let steered = steer(velocity, Vec3::new(0., 0., 5.), PI);
+        assert!(steered.abs_diff_eq(Vec3::new(0., 0., 10.), 1e-4));
+    }
+}
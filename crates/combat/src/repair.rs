@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use de_core::{objects::ObjectType, player::Player, stages::GameStage, state::GameState};
+use de_economy::PowerSupply;
+use de_index::SpatialQuery;
+use de_objects::{ColliderCache, Health, ObjectCache, Regeneration, Repairer};
+use de_spawner::SpawnerLabels;
+use iyes_loopless::prelude::*;
+
+use crate::{damage::Susceptible, AttackingLabels};
+
+pub(crate) struct RepairPlugin;
+
+impl Plugin for RepairPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set_to_stage(
+            GameStage::Update,
+            SystemSet::new()
+                .with_system(
+                    regenerate
+                        .run_in_state(GameState::Playing)
+                        .label(RepairLabel::Regenerate)
+                        .after(AttackingLabels::Explode)
+                        .before(SpawnerLabels::Destroyer),
+                )
+                .with_system(
+                    repair
+                        .run_in_state(GameState::Playing)
+                        .after(RepairLabel::Regenerate)
+                        .before(SpawnerLabels::Destroyer),
+                ),
+        );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemLabel)]
+enum RepairLabel {
+    Regenerate,
+}
+
+fn regenerate(
+    time: Res<Time>,
+    entities: Query<(Entity, &Regeneration)>,
+    mut susceptible: Susceptible,
+) {
+    let time_delta = time.delta_seconds();
+    for (entity, regeneration) in entities.iter() {
+        susceptible.heal(entity, time_delta * regeneration.rate());
+    }
+}
+
+/// Repairers heal friendly objects in their range. Repairers which consume
+/// energy repair proportionally slower when they are not fully powered.
+fn repair(
+    time: Res<Time>,
+    cache: Res<ObjectCache>,
+    repairers: Query<(
+        Entity,
+        &ObjectType,
+        &Transform,
+        &Player,
+        &Repairer,
+        Option<&PowerSupply>,
+    )>,
+    entities: SpatialQuery<(Entity, &Player), With<Health>>,
+    mut susceptible: Susceptible,
+) {
+    let time_delta = time.delta_seconds();
+
+    for (repairer, &object_type, transform, &player, params, supply) in repairers.iter() {
+        if susceptible
+            .health(repairer)
+            .map_or(true, |health| health.destroyed())
+        {
+            continue;
+        }
+
+        let rate = params.rate() * supply.map_or(1., |supply| supply.efficiency());
+        let centroid: Vec3 = cache.get_collider(object_type).aabb().center().into();
+        let center = transform.translation + centroid;
+        for intersection in entities.query_ball(&center.into(), params.range(), Some(repairer)) {
+            let (entity, &entity_player) = *intersection.item();
+            if entity_player == player {
+                susceptible.heal(entity, time_delta * rate);
+            }
+        }
+    }
+}
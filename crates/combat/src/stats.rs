@@ -0,0 +1,634 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::*;
+use de_core::{
+    objects::{ActiveObjectType, ObjectType},
+    player::Player,
+    stages::GameStage,
+    state::GameState,
+};
+use de_spawner::{ObjectDestroyedEvent, SpawnerLabels};
+use enum_map::EnumMap;
+use iyes_loopless::prelude::*;
+use serde::{Serialize, Serializer};
+
+use crate::damage::HitEvent;
+
+/// Name of the file (in the export directory, see [`StatsExport`]) combat
+/// statistics are stored to at the end of each game.
+const EXPORT_FILE_NAME: &str = "combat_stats.json";
+/// Maximum number of records in the damage log. The oldest records are
+/// dropped once the log is full.
+const MAX_DAMAGE_LOG_LENGTH: usize = 50_000;
+
+pub(crate) struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ShotEvent>()
+            .add_enter_system(GameState::Playing, setup)
+            .add_exit_system(GameState::Playing, export)
+            .add_system_set_to_stage(
+                GameStage::Update,
+                SystemSet::new()
+                    .with_system(
+                        shots
+                            .run_in_state(GameState::Playing)
+                            .after(SpawnerLabels::Destroyer),
+                    )
+                    .with_system(
+                        hits.run_in_state(GameState::Playing)
+                            .after(SpawnerLabels::Destroyer),
+                    )
+                    .with_system(
+                        kills
+                            .run_in_state(GameState::Playing)
+                            .after(SpawnerLabels::Destroyer),
+                    ),
+            );
+    }
+}
+
+/// Combat statistics are exported to a JSON file in this directory at the
+/// end of each game. Statistics are not exported unless this resource is
+/// present.
+pub struct StatsExport {
+    dir: PathBuf,
+}
+
+impl StatsExport {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        self.dir.as_path()
+    }
+}
+
+/// This event is sent whenever a shot fired by a cannon is resolved, i.e.
+/// when a laser is fired or when a projectile impacts.
+pub(crate) struct ShotEvent {
+    attacker: Entity,
+    hit: bool,
+}
+
+impl ShotEvent {
+    /// # Arguments
+    ///
+    /// * `attacker` - the firing entity.
+    ///
+    /// * `hit` - true if an object which can be damaged was hit.
+    pub(crate) fn new(attacker: Entity, hit: bool) -> Self {
+        Self { attacker, hit }
+    }
+}
+
+/// Combat statistics of the current game, aggregated per player and per
+/// object, together with time ordered logs of dealt damage and of destroyed
+/// objects.
+///
+/// Only a limited number of the latest damage records is kept so that the
+/// memory usage does not grow without bound during long games.
+#[derive(Default, Serialize)]
+pub struct CombatStats {
+    #[serde(skip)]
+    start: f64,
+    players: HashMap<Player, PlayerStats>,
+    #[serde(serialize_with = "serialize_values")]
+    objects: HashMap<Entity, ObjectStats>,
+    damage_log: VecDeque<DamageRecord>,
+    /// Number of records dropped from the beginning of the damage log.
+    damage_log_dropped: usize,
+    kill_log: Vec<KillRecord>,
+}
+
+impl CombatStats {
+    fn new(start: f64) -> Self {
+        Self {
+            start,
+            ..Default::default()
+        }
+    }
+
+    /// Returns statistics of a player or None if the player has not taken
+    /// part in any combat yet.
+    pub fn player(&self, player: Player) -> Option<&PlayerStats> {
+        self.players.get(&player)
+    }
+
+    /// Returns statistics of an active object or None if the object has not
+    /// taken part in any combat yet. Statistics of destroyed objects are
+    /// kept.
+    pub fn object(&self, entity: Entity) -> Option<&ObjectStats> {
+        self.objects.get(&entity)
+    }
+
+    /// Returns damage dealt to active objects in chronological order. Only
+    /// the latest records are returned, see [`Self::damage_log_dropped`].
+    pub fn damage_log(&self) -> impl ExactSizeIterator<Item = &DamageRecord> {
+        self.damage_log.iter()
+    }
+
+    /// Returns the number of the oldest damage records which were dropped
+    /// from the damage log. Aggregated statistics include the dropped
+    /// records.
+    pub fn damage_log_dropped(&self) -> usize {
+        self.damage_log_dropped
+    }
+
+    /// Returns all destroyed active objects in chronological order.
+    pub fn kill_log(&self) -> &[KillRecord] {
+        self.kill_log.as_slice()
+    }
+
+    /// Serializes the statistics to JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    fn participant(&mut self, participant: Participant) -> (&mut PlayerStats, &mut ObjectStats) {
+        let player = self.players.entry(participant.player).or_default();
+        let object = self
+            .objects
+            .entry(participant.entity)
+            .or_insert_with(|| ObjectStats::new(participant.object_type, participant.player));
+        (player, object)
+    }
+
+    fn record_shot(&mut self, attacker: Participant, hit: bool) {
+        let (player, object) = self.participant(attacker);
+        player.shots_fired += 1;
+        object.shots_fired += 1;
+        if hit {
+            player.shots_hit += 1;
+            object.shots_hit += 1;
+        }
+    }
+
+    fn record_hit(
+        &mut self,
+        time: f32,
+        attacker: Option<Participant>,
+        target: Participant,
+        damage: f32,
+    ) {
+        if let Some(attacker) = attacker {
+            let (player, object) = self.participant(attacker);
+            player.damage_dealt += damage;
+            object.damage_dealt += damage;
+        }
+
+        let (player, object) = self.participant(target);
+        player.damage_received += damage;
+        object.damage_received += damage;
+
+        if self.damage_log.len() >= MAX_DAMAGE_LOG_LENGTH {
+            self.damage_log.pop_front();
+            self.damage_log_dropped += 1;
+        }
+        self.damage_log.push_back(DamageRecord {
+            time,
+            attacker: attacker.map(ParticipantRecord::from),
+            target: target.into(),
+            damage,
+        });
+    }
+
+    fn record_kill(&mut self, time: f32, killer: Option<Participant>, victim: Participant) {
+        if let Some(killer) = killer {
+            let (player, object) = self.participant(killer);
+            player.kills[victim.object_type] += 1;
+            object.kills += 1;
+        }
+
+        let (player, object) = self.participant(victim);
+        player.losses[victim.object_type] += 1;
+        object.destroyed = true;
+
+        self.kill_log.push(KillRecord {
+            time,
+            killer: killer.map(ParticipantRecord::from),
+            victim: victim.into(),
+        });
+    }
+
+    /// Returns the participant corresponding to an entity. Entities which
+    /// already took part in combat are found even if they no longer exist.
+    fn resolve(
+        &self,
+        entity: Entity,
+        objects: &Query<(&ObjectType, &Player)>,
+    ) -> Option<Participant> {
+        if let Some(stats) = self.objects.get(&entity) {
+            return Some(Participant::new(entity, stats.object_type, stats.player));
+        }
+
+        match objects.get(entity) {
+            Ok((&ObjectType::Active(object_type), &player)) => {
+                Some(Participant::new(entity, object_type, player))
+            }
+            _ => None,
+        }
+    }
+
+    fn time(&self, time: &Time) -> f32 {
+        (time.seconds_since_startup() - self.start) as f32
+    }
+}
+
+/// Aggregated combat statistics of a single player.
+#[derive(Default, Serialize)]
+pub struct PlayerStats {
+    damage_dealt: f32,
+    damage_received: f32,
+    shots_fired: u32,
+    shots_hit: u32,
+    #[serde(serialize_with = "serialize_counts")]
+    kills: EnumMap<ActiveObjectType, u32>,
+    #[serde(serialize_with = "serialize_counts")]
+    losses: EnumMap<ActiveObjectType, u32>,
+}
+
+impl PlayerStats {
+    /// Total damage dealt by objects of the player (including friendly
+    /// fire).
+    pub fn damage_dealt(&self) -> f32 {
+        self.damage_dealt
+    }
+
+    /// Total damage received by objects of the player.
+    pub fn damage_received(&self) -> f32 {
+        self.damage_received
+    }
+
+    pub fn shots_fired(&self) -> u32 {
+        self.shots_fired
+    }
+
+    /// Number of fired shots which hit an object which can be damaged.
+    pub fn shots_hit(&self) -> u32 {
+        self.shots_hit
+    }
+
+    /// Number of objects of a given type destroyed by the player.
+    pub fn kills(&self, object_type: ActiveObjectType) -> u32 {
+        self.kills[object_type]
+    }
+
+    /// Number of objects of a given type of the player which were destroyed.
+    pub fn losses(&self, object_type: ActiveObjectType) -> u32 {
+        self.losses[object_type]
+    }
+}
+
+/// Combat statistics of a single active object.
+#[derive(Serialize)]
+pub struct ObjectStats {
+    object_type: ActiveObjectType,
+    player: Player,
+    damage_dealt: f32,
+    damage_received: f32,
+    shots_fired: u32,
+    shots_hit: u32,
+    kills: u32,
+    destroyed: bool,
+}
+
+impl ObjectStats {
+    fn new(object_type: ActiveObjectType, player: Player) -> Self {
+        Self {
+            object_type,
+            player,
+            damage_dealt: 0.,
+            damage_received: 0.,
+            shots_fired: 0,
+            shots_hit: 0,
+            kills: 0,
+            destroyed: false,
+        }
+    }
+
+    pub fn object_type(&self) -> ActiveObjectType {
+        self.object_type
+    }
+
+    pub fn player(&self) -> Player {
+        self.player
+    }
+
+    pub fn damage_dealt(&self) -> f32 {
+        self.damage_dealt
+    }
+
+    pub fn damage_received(&self) -> f32 {
+        self.damage_received
+    }
+
+    pub fn shots_fired(&self) -> u32 {
+        self.shots_fired
+    }
+
+    pub fn shots_hit(&self) -> u32 {
+        self.shots_hit
+    }
+
+    /// Number of objects destroyed by the object.
+    pub fn kills(&self) -> u32 {
+        self.kills
+    }
+
+    pub fn destroyed(&self) -> bool {
+        self.destroyed
+    }
+}
+
+/// A single instance of damage dealt to an active object.
+#[derive(Serialize)]
+pub struct DamageRecord {
+    time: f32,
+    attacker: Option<ParticipantRecord>,
+    target: ParticipantRecord,
+    damage: f32,
+}
+
+impl DamageRecord {
+    /// Time in seconds since the start of the game.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// The damaging object or None if the damage was not caused by an
+    /// object.
+    pub fn attacker(&self) -> Option<&ParticipantRecord> {
+        self.attacker.as_ref()
+    }
+
+    pub fn target(&self) -> &ParticipantRecord {
+        &self.target
+    }
+
+    /// Damage after application of armor based multipliers.
+    pub fn damage(&self) -> f32 {
+        self.damage
+    }
+}
+
+/// Destruction of a single active object.
+#[derive(Serialize)]
+pub struct KillRecord {
+    time: f32,
+    killer: Option<ParticipantRecord>,
+    victim: ParticipantRecord,
+}
+
+impl KillRecord {
+    /// Time in seconds since the start of the game.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// The object which dealt the last damage to the victim or None if it
+    /// is not known.
+    pub fn killer(&self) -> Option<&ParticipantRecord> {
+        self.killer.as_ref()
+    }
+
+    pub fn victim(&self) -> &ParticipantRecord {
+        &self.victim
+    }
+}
+
+/// An object referenced from [`DamageRecord`] or [`KillRecord`].
+#[derive(Serialize)]
+pub struct ParticipantRecord {
+    object_type: ActiveObjectType,
+    player: Player,
+}
+
+impl ParticipantRecord {
+    pub fn object_type(&self) -> ActiveObjectType {
+        self.object_type
+    }
+
+    pub fn player(&self) -> Player {
+        self.player
+    }
+}
+
+impl From<Participant> for ParticipantRecord {
+    fn from(participant: Participant) -> Self {
+        Self {
+            object_type: participant.object_type,
+            player: participant.player,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Participant {
+    entity: Entity,
+    object_type: ActiveObjectType,
+    player: Player,
+}
+
+impl Participant {
+    fn new(entity: Entity, object_type: ActiveObjectType, player: Player) -> Self {
+        Self {
+            entity,
+            object_type,
+            player,
+        }
+    }
+}
+
+fn serialize_values<S, K, V>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Serialize,
+{
+    serializer.collect_seq(map.values())
+}
+
+fn serialize_counts<S>(
+    counts: &EnumMap<ActiveObjectType, u32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_map(
+        counts
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(object_type, count)| (object_type.to_string(), count)),
+    )
+}
+
+fn setup(mut commands: Commands, time: Res<Time>) {
+    commands.insert_resource(CombatStats::new(time.seconds_since_startup()));
+}
+
+fn export(mut commands: Commands, config: Option<Res<StatsExport>>, stats: Res<CombatStats>) {
+    commands.remove_resource::<CombatStats>();
+
+    let dir = match config {
+        Some(ref config) => config.dir(),
+        None => return,
+    };
+    let json = match stats.to_json() {
+        Ok(json) => json,
+        Err(error) => {
+            error!("Failed to serialize combat statistics: {}", error);
+            return;
+        }
+    };
+
+    let path = dir.join(EXPORT_FILE_NAME);
+    match fs::create_dir_all(dir).and_then(|_| fs::write(path.as_path(), json)) {
+        Ok(_) => info!("Combat statistics stored to {}", path.display()),
+        Err(error) => error!("Failed to store combat statistics: {}", error),
+    }
+}
+
+fn shots(
+    mut stats: ResMut<CombatStats>,
+    mut events: EventReader<ShotEvent>,
+    objects: Query<(&ObjectType, &Player)>,
+) {
+    for event in events.iter() {
+        if let Some(attacker) = stats.resolve(event.attacker, &objects) {
+            stats.record_shot(attacker, event.hit);
+        }
+    }
+}
+
+fn hits(
+    time: Res<Time>,
+    mut stats: ResMut<CombatStats>,
+    mut events: EventReader<HitEvent>,
+    objects: Query<(&ObjectType, &Player)>,
+) {
+    let time = stats.time(&time);
+    for event in events.iter() {
+        let target = match stats.resolve(event.target(), &objects) {
+            Some(target) => target,
+            None => continue,
+        };
+        let attacker = event
+            .attacker()
+            .and_then(|attacker| stats.resolve(attacker, &objects));
+        stats.record_hit(time, attacker, target, event.damage());
+    }
+}
+
+fn kills(
+    time: Res<Time>,
+    mut stats: ResMut<CombatStats>,
+    mut events: EventReader<ObjectDestroyedEvent>,
+    objects: Query<(&ObjectType, &Player)>,
+) {
+    let time = stats.time(&time);
+    for event in events.iter() {
+        let (object_type, player) = match (event.object_type(), event.player()) {
+            (ObjectType::Active(object_type), Some(player)) => (object_type, player),
+            _ => continue,
+        };
+
+        let victim = Participant::new(event.entity(), object_type, player);
+        let killer = event
+            .killer()
+            .and_then(|killer| stats.resolve(killer, &objects));
+        stats.record_kill(time, killer, victim);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use de_core::objects::{BuildingType, UnitType};
+
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let attacker = Participant::new(
+            Entity::from_raw(1),
+            ActiveObjectType::Unit(UnitType::Attacker),
+            Player::Player1,
+        );
+        let target = Participant::new(
+            Entity::from_raw(2),
+            ActiveObjectType::Building(BuildingType::PowerHub),
+            Player::Player2,
+        );
+
+        let mut stats = CombatStats::new(0.);
+        stats.record_shot(attacker, true);
+        stats.record_shot(attacker, false);
+        stats.record_hit(1., Some(attacker), target, 20.);
+        stats.record_hit(2., None, target, 5.);
+        stats.record_kill(3., Some(attacker), target);
+
+        let player = stats.player(Player::Player1).unwrap();
+        assert_eq!(player.shots_fired(), 2);
+        assert_eq!(player.shots_hit(), 1);
+        assert_eq!(player.damage_dealt(), 20.);
+        assert_eq!(player.damage_received(), 0.);
+        assert_eq!(
+            player.kills(ActiveObjectType::Building(BuildingType::PowerHub)),
+            1
+        );
+
+        let player = stats.player(Player::Player2).unwrap();
+        assert_eq!(player.damage_received(), 25.);
+        assert_eq!(
+            player.losses(ActiveObjectType::Building(BuildingType::PowerHub)),
+            1
+        );
+
+        let object = stats.object(Entity::from_raw(2)).unwrap();
+        assert_eq!(object.damage_received(), 25.);
+        assert!(object.destroyed());
+        assert_eq!(stats.object(Entity::from_raw(1)).unwrap().kills(), 1);
+
+        assert_eq!(stats.damage_log().len(), 2);
+        assert!(stats.damage_log().nth(1).unwrap().attacker().is_none());
+        assert_eq!(stats.damage_log_dropped(), 0);
+        assert_eq!(stats.kill_log().len(), 1);
+        assert_eq!(stats.kill_log()[0].time(), 3.);
+
+        let json = stats.to_json().unwrap();
+        assert!(json.contains("\"Building -> Power Hub\": 1"));
+    }
+
+    #[test]
+    fn test_damage_log_limit() {
+        let attacker = Participant::new(
+            Entity::from_raw(1),
+            ActiveObjectType::Unit(UnitType::Attacker),
+            Player::Player1,
+        );
+        let target = Participant::new(
+            Entity::from_raw(2),
+            ActiveObjectType::Unit(UnitType::Attacker),
+            Player::Player2,
+        );
+
+        let mut stats = CombatStats::new(0.);
+        for i in 0..(MAX_DAMAGE_LOG_LENGTH + 10) {
+            stats.record_hit(i as f32, Some(attacker), target, 1.);
+        }
+
+        assert_eq!(stats.damage_log().len(), MAX_DAMAGE_LOG_LENGTH);
+        assert_eq!(stats.damage_log_dropped(), 10);
+        assert_eq!(stats.damage_log().next().unwrap().time(), 10.);
+        assert_eq!(
+            stats.player(Player::Player1).unwrap().damage_dealt(),
+            (MAX_DAMAGE_LOG_LENGTH + 10) as f32
+        );
+    }
+}
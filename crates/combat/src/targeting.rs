@@ -0,0 +1,272 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use de_core::{objects::ObjectType, player::Player, stages::GameStage, state::GameState};
+use de_objects::{Armament, Health, TargetCriterion, TargetFilter, TargetPriorities};
+use iyes_loopless::prelude::*;
+
+pub(crate) struct TargetingPlugin;
+
+impl Plugin for TargetingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<FocusEvent>()
+            .add_enter_system(GameState::Playing, setup)
+            .add_exit_system(GameState::Playing, destruct)
+            .add_system_to_stage(GameStage::PreUpdate, focus.run_in_state(GameState::Playing));
+    }
+}
+
+/// Send this event to make all objects of a player prefer an enemy during
+/// target selection, see [`TargetCriterion::Focus`].
+pub struct FocusEvent {
+    player: Player,
+    target: Entity,
+}
+
+impl FocusEvent {
+    pub fn new(player: Player, target: Entity) -> Self {
+        Self { player, target }
+    }
+}
+
+/// Enemies focused by individual players.
+#[derive(Default)]
+pub struct FocusTargets(HashMap<Player, Entity>);
+
+impl FocusTargets {
+    /// Returns the enemy focused by a player or None if the player has not
+    /// focused any enemy. The returned entity might no longer exist.
+    pub fn get(&self, player: Player) -> Option<Entity> {
+        self.0.get(&player).copied()
+    }
+}
+
+/// System parameter used to choose targets among enemies.
+#[derive(SystemParam)]
+pub(crate) struct Targeting<'w, 's> {
+    focus: Res<'w, FocusTargets>,
+    details: Query<'w, 's, (&'static Health, Option<&'static Armament>)>,
+}
+
+impl<'w, 's> Targeting<'w, 's> {
+    /// Returns a target selector of an attacker.
+    ///
+    /// # Arguments
+    ///
+    /// * `priorities` - target selection rules of the attacker.
+    ///
+    /// * `player` - owner of the attacker.
+    pub(crate) fn selector<'a>(
+        &self,
+        priorities: &'a TargetPriorities,
+        player: Player,
+    ) -> TargetSelector<'a> {
+        TargetSelector::new(priorities, self.focus.get(player))
+    }
+
+    /// Returns a target selection candidate or None if the entity does not
+    /// exist or cannot be damaged.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - the potential target.
+    ///
+    /// * `object_type` - type of the potential target.
+    ///
+    /// * `distance` - distance between the attacker and the potential target.
+    pub(crate) fn candidate(
+        &self,
+        entity: Entity,
+        object_type: ObjectType,
+        distance: f32,
+    ) -> Option<Candidate> {
+        self.details.get(entity).ok().map(|(health, armament)| {
+            Candidate::new(
+                entity,
+                object_type,
+                distance,
+                armament.is_some(),
+                health.fraction(),
+            )
+        })
+    }
+}
+
+/// A potential target considered during target selection.
+pub(crate) struct Candidate {
+    entity: Entity,
+    object_type: ObjectType,
+    distance: f32,
+    armed: bool,
+    health: f32,
+}
+
+impl Candidate {
+    /// # Arguments
+    ///
+    /// * `entity` - the potential target.
+    ///
+    /// * `object_type` - type of the potential target.
+    ///
+    /// * `distance` - distance between the attacker and the potential target.
+    ///
+    /// * `armed` - whether the potential target is able to attack.
+    ///
+    /// * `health` - fraction of remaining health of the potential target.
+    pub(crate) fn new(
+        entity: Entity,
+        object_type: ObjectType,
+        distance: f32,
+        armed: bool,
+        health: f32,
+    ) -> Self {
+        Self {
+            entity,
+            object_type,
+            distance,
+            armed,
+            health,
+        }
+    }
+}
+
+/// Chooses a target among several candidates according to
+/// [`TargetPriorities`] of the attacker.
+pub(crate) struct TargetSelector<'a> {
+    priorities: &'a TargetPriorities,
+    focus: Option<Entity>,
+}
+
+impl<'a> TargetSelector<'a> {
+    /// # Arguments
+    ///
+    /// * `priorities` - target selection rules of the attacker.
+    ///
+    /// * `focus` - enemy focused by the player of the attacker, if any.
+    pub(crate) fn new(priorities: &'a TargetPriorities, focus: Option<Entity>) -> Self {
+        Self { priorities, focus }
+    }
+
+    /// Returns the most preferred candidate or None if there is no
+    /// candidate.
+    pub(crate) fn select<I>(&self, candidates: I) -> Option<Entity>
+    where
+        I: IntoIterator<Item = Candidate>,
+    {
+        candidates
+            .into_iter()
+            .min_by(|a, b| self.compare(a, b))
+            .map(|candidate| candidate.entity)
+    }
+
+    /// Returns [`Ordering::Less`] if candidate `a` is preferred over
+    /// candidate `b`.
+    fn compare(&self, a: &Candidate, b: &Candidate) -> Ordering {
+        for &criterion in self.priorities.criteria() {
+            let ordering = match criterion {
+                TargetCriterion::Focus => {
+                    preferred(Some(a.entity) == self.focus, Some(b.entity) == self.focus)
+                }
+                TargetCriterion::Threat => preferred(a.armed, b.armed),
+                TargetCriterion::Units => preferred(is_unit(a), is_unit(b)),
+                TargetCriterion::Buildings => preferred(!is_unit(a), !is_unit(b)),
+                TargetCriterion::LowestHealth => a.health.total_cmp(&b.health),
+                TargetCriterion::Closest => a.distance.total_cmp(&b.distance),
+            };
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        a.distance.total_cmp(&b.distance)
+    }
+}
+
+/// Returns [`Ordering::Less`] if only `a` has a preferred property.
+fn preferred(a: bool, b: bool) -> Ordering {
+    b.cmp(&a)
+}
+
+fn is_unit(candidate: &Candidate) -> bool {
+    TargetFilter::Units.matches(candidate.object_type)
+}
+
+fn setup(mut commands: Commands) {
+    commands.insert_resource(FocusTargets::default());
+}
+
+fn destruct(mut commands: Commands) {
+    commands.remove_resource::<FocusTargets>();
+}
+
+fn focus(mut events: EventReader<FocusEvent>, mut targets: ResMut<FocusTargets>) {
+    for event in events.iter() {
+        targets.0.insert(event.player, event.target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use de_core::objects::{ActiveObjectType, BuildingType, UnitType};
+
+    use super::*;
+
+    fn candidates() -> Vec<Candidate> {
+        let unit = ObjectType::Active(ActiveObjectType::Unit(UnitType::Attacker));
+        let building = ObjectType::Active(ActiveObjectType::Building(BuildingType::Base));
+        vec![
+            Candidate::new(Entity::from_raw(1), building, 10., false, 0.2),
+            Candidate::new(Entity::from_raw(2), unit, 20., true, 0.8),
+            Candidate::new(Entity::from_raw(3), unit, 30., true, 0.5),
+            Candidate::new(Entity::from_raw(4), building, 5., false, 1.),
+        ]
+    }
+
+    #[test]
+    fn test_select() {
+        let default = TargetPriorities::default();
+        assert_eq!(
+            TargetSelector::new(&default, None).select(candidates()),
+            Some(Entity::from_raw(3))
+        );
+        assert_eq!(
+            TargetSelector::new(&default, Some(Entity::from_raw(4))).select(candidates()),
+            Some(Entity::from_raw(4))
+        );
+        // Focused entity which is not among the candidates.
+        assert_eq!(
+            TargetSelector::new(&default, Some(Entity::from_raw(7))).select(candidates()),
+            Some(Entity::from_raw(3))
+        );
+
+        let closest = TargetPriorities::new(vec![TargetCriterion::Closest]);
+        assert_eq!(
+            TargetSelector::new(&closest, None).select(candidates()),
+            Some(Entity::from_raw(4))
+        );
+
+        let health = TargetPriorities::new(vec![TargetCriterion::LowestHealth]);
+        assert_eq!(
+            TargetSelector::new(&health, None).select(candidates()),
+            Some(Entity::from_raw(1))
+        );
+
+        let buildings = TargetPriorities::new(vec![
+            TargetCriterion::Buildings,
+            TargetCriterion::LowestHealth,
+        ]);
+        assert_eq!(
+            TargetSelector::new(&buildings, None).select(candidates()),
+            Some(Entity::from_raw(1))
+        );
+
+        let units = TargetPriorities::new(vec![TargetCriterion::Units]);
+        assert_eq!(
+            TargetSelector::new(&units, None).select(candidates()),
+            Some(Entity::from_raw(2))
+        );
+
+        assert_eq!(TargetSelector::new(&default, None).select(vec![]), None);
+    }
+}
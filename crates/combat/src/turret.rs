@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use de_core::{stages::GameStage, state::GameState};
+use de_objects::Turret;
+use iyes_loopless::prelude::*;
+
+pub(crate) struct TurretPlugin;
+
+impl Plugin for TurretPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            GameStage::PostUpdate,
+            orient.run_in_state(GameState::Playing),
+        );
+    }
+}
+
+/// Rotates models of turrets according to turret heading. Models of objects
+/// with a turret are spawned as child entities, see
+/// [`de_spawner::SpawnBundle`].
+fn orient(
+    turrets: Query<(&Turret, &Children), Changed<Turret>>,
+    mut models: Query<&mut Transform, With<Handle<Scene>>>,
+) {
+    for (turret, children) in turrets.iter() {
+        for &child in children.iter() {
+            if let Ok(mut transform) = models.get_mut(child) {
+                transform.rotation = turret.rotation();
+            }
+        }
+    }
+}
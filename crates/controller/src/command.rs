@@ -1,17 +1,24 @@
 use bevy::prelude::*;
-use de_behaviour::ChaseTarget;
-use de_combat::AttackEvent;
+use de_behaviour::{Order, OrderEvent, OrderMode, Stance};
+use de_combat::FocusEvent;
 use de_core::{
     gconfig::GameConfig,
-    objects::{BuildingType, MovableSolid, Playable, PLAYER_MAX_BUILDINGS},
+    objects::{
+        ActiveObjectType, BuildingType, MovableSolid, ObjectType, Playable, UnitType,
+        PLAYER_MAX_BUILDINGS,
+    },
     player::Player,
     projection::ToFlat,
     screengeom::ScreenRect,
     stages::GameStage,
     state::GameState,
 };
-use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPath};
-use de_spawner::{Draft, ObjectCounter};
+use de_economy::{CancelConstructionEvent, Stockpiles};
+use de_objects::{Factory, ObjectCache};
+use de_production::{
+    ProductionCommand, ProductionEvent, ProductionQueue, RallyPoint, SetRallyPointEvent,
+};
+use de_spawner::{Draft, ObjectCounter, UnderConstruction};
 use enum_map::enum_map;
 use iyes_loopless::prelude::*;
 
@@ -31,6 +38,7 @@ impl CommandPlugin {
         let key_map = enum_map! {
             BuildingType::Base => KeyCode::B,
             BuildingType::PowerHub => KeyCode::P,
+            BuildingType::Turret => KeyCode::T,
         };
         key_map
             .iter()
@@ -43,6 +51,50 @@ impl CommandPlugin {
                 )
             })
     }
+
+    fn stance_systems() -> SystemSet {
+        let key_map = enum_map! {
+            Stance::HoldFire => KeyCode::F1,
+            Stance::HoldPosition => KeyCode::F2,
+            Stance::Defensive => KeyCode::F3,
+            Stance::Aggressive => KeyCode::F4,
+        };
+        key_map
+            .iter()
+            .fold(SystemSet::new(), |systems, (stance, &key)| {
+                systems.with_system(
+                    set_stance(stance)
+                        .run_in_state(GameState::Playing)
+                        .run_if(KeyCondition::single(key).build()),
+                )
+            })
+    }
+
+    fn production_systems() -> SystemSet {
+        let key_map = enum_map! {
+            UnitType::Attacker => KeyCode::U,
+        };
+        key_map.iter().fold(
+            SystemSet::new().with_system(
+                cancel_production
+                    .run_in_state(GameState::Playing)
+                    .run_if(KeyCondition::single(KeyCode::Back).build()),
+            ),
+            |systems, (unit_type, &key)| {
+                systems
+                    .with_system(
+                        enqueue(unit_type, false)
+                            .run_in_state(GameState::Playing)
+                            .run_if(KeyCondition::single(key).build()),
+                    )
+                    .with_system(
+                        enqueue(unit_type, true)
+                            .run_in_state(GameState::Playing)
+                            .run_if(KeyCondition::single(key).with_shift().build()),
+                    )
+            },
+        )
+    }
 }
 
 impl Plugin for CommandPlugin {
@@ -57,6 +109,13 @@ impl Plugin for CommandPlugin {
                         .after(PointerLabels::Update)
                         .after(MouseLabels::Buttons),
                 )
+                .with_system(
+                    set_rally_point
+                        .run_in_state(GameState::Playing)
+                        .run_if(on_click(MouseButton::Right))
+                        .after(PointerLabels::Update)
+                        .after(MouseLabels::Buttons),
+                )
                 .with_system(
                     left_click_handler
                         .run_in_state(GameState::Playing)
@@ -72,6 +131,17 @@ impl Plugin for CommandPlugin {
                         .run_if(KeyCondition::single(KeyCode::Escape).build())
                         .before(DraftLabels::Discard),
                 )
+                .with_system(
+                    cancel_construction
+                        .run_in_state(GameState::Playing)
+                        .run_if(KeyCondition::single(KeyCode::Delete).build()),
+                )
+                .with_system(
+                    focus
+                        .run_in_state(GameState::Playing)
+                        .run_if(KeyCondition::single(KeyCode::F).build())
+                        .after(PointerLabels::Update),
+                )
                 .with_system(
                     select_all
                         .run_in_state(GameState::Playing)
@@ -90,7 +160,9 @@ impl Plugin for CommandPlugin {
                         .before(AreaSelectLabels::SelectInArea),
                 ),
         )
-        .add_system_set_to_stage(GameStage::Input, Self::place_draft_systems());
+        .add_system_set_to_stage(GameStage::Input, Self::place_draft_systems())
+        .add_system_set_to_stage(GameStage::Input, Self::stance_systems())
+        .add_system_set_to_stage(GameStage::Input, Self::production_systems());
     }
 }
 
@@ -102,58 +174,113 @@ fn on_click(button: MouseButton) -> impl Fn(EventReader<MouseClicked>) -> bool {
     }
 }
 
-type SelectedQuery<'w, 's> =
-    Query<'w, 's, (Entity, Option<&'static ChaseTarget>), (With<Selected>, With<MovableSolid>)>;
-
+/// Right click on an enemy orders selected units to attack it. Right click on
+/// a friendly object orders selected units to follow it, units guard it if
+/// ALT is pressed. Right click on the terrain moves selected units, units
+/// attack-move there if ALT is pressed or patrol between their position and
+/// there if CTRL is pressed. The order is queued after already given orders
+/// if SHIFT is pressed.
 fn right_click_handler(
-    mut commands: Commands,
     config: Res<GameConfig>,
-    mut path_events: EventWriter<UpdateEntityPath>,
-    mut attack_events: EventWriter<AttackEvent>,
-    selected: SelectedQuery,
+    keys: Res<Input<KeyCode>>,
+    mut events: EventWriter<OrderEvent>,
+    selected: Query<Entity, (With<Selected>, With<MovableSolid>)>,
     targets: Query<&Player>,
     pointer: Res<Pointer>,
 ) {
-    match pointer.entity().filter(|&entity| {
-        targets
-            .get(entity)
-            .map(|&player| !config.is_local_player(player))
-            .unwrap_or(false)
-    }) {
-        Some(enemy) => {
-            for (attacker, _) in selected.iter() {
-                attack_events.send(AttackEvent::new(attacker, enemy));
+    let alt = keys.pressed(KeyCode::LAlt) || keys.pressed(KeyCode::RAlt);
+
+    let order = match pointer
+        .entity()
+        .and_then(|entity| targets.get(entity).ok().map(|&player| (entity, player)))
+    {
+        Some((target, player)) if config.is_local_player(player) => {
+            if alt {
+                Order::Guard(target)
+            } else {
+                Order::Follow(target)
             }
         }
+        Some((enemy, _)) => Order::Attack(enemy),
         None => {
             let target = match pointer.terrain_point() {
                 Some(point) => point.to_flat(),
                 None => return,
             };
 
-            for (entity, chase) in selected.iter() {
-                if chase.is_some() {
-                    commands.entity(entity).remove::<ChaseTarget>();
-                }
-
-                path_events.send(UpdateEntityPath::new(
-                    entity,
-                    PathTarget::new(target, PathQueryProps::exact(), false),
-                ));
+            if alt {
+                Order::AttackMove(target)
+            } else if keys.pressed(KeyCode::LControl) || keys.pressed(KeyCode::RControl) {
+                Order::Patrol(target)
+            } else {
+                Order::Move(target)
             }
         }
+    };
+
+    let mode = if keys.pressed(KeyCode::LShift) || keys.pressed(KeyCode::RShift) {
+        OrderMode::Append
+    } else {
+        OrderMode::Replace
+    };
+
+    for entity in selected.iter() {
+        events.send(OrderEvent::new(entity, order, mode));
+    }
+}
+
+/// Right click sets the rally point of selected buildings which produce
+/// units. The rally point is the pointed to object or the pointed to location
+/// on the terrain.
+fn set_rally_point(
+    pointer: Res<Pointer>,
+    mut events: EventWriter<SetRallyPointEvent>,
+    selected: Query<Entity, (With<Selected>, With<Factory>)>,
+    targets: Query<(), With<Player>>,
+) {
+    let rally_point = match pointer.entity().filter(|&entity| targets.contains(entity)) {
+        Some(target) => RallyPoint::Target(target),
+        None => match pointer.terrain_point() {
+            Some(point) => RallyPoint::Location(point.to_flat()),
+            None => return,
+        },
+    };
+
+    for entity in selected.iter() {
+        events.send(SetRallyPointEvent::new(entity, rally_point));
+    }
+}
+
+/// Makes all objects of the local player prefer the pointed to enemy when they
+/// choose their targets.
+fn focus(
+    config: Res<GameConfig>,
+    pointer: Res<Pointer>,
+    targets: Query<&Player>,
+    mut events: EventWriter<FocusEvent>,
+) {
+    let enemy = pointer.entity().filter(|&entity| {
+        targets
+            .get(entity)
+            .map(|&player| !config.is_local_player(player))
+            .unwrap_or(false)
+    });
+
+    if let Some(enemy) = enemy {
+        events.send(FocusEvent::new(config.player(), enemy));
     }
 }
 
 fn left_click_handler(
     mut select_events: EventWriter<SelectEvent>,
     mut draft_events: EventWriter<SpawnDraftsEvent>,
+    config: Res<GameConfig>,
     keys: Res<Input<KeyCode>>,
     pointer: Res<Pointer>,
     playable: Query<(), With<Playable>>,
-    drafts: Query<(), With<Draft>>,
+    drafts: Query<&Player, With<Draft>>,
 ) {
-    if drafts.is_empty() {
+    if !drafts.iter().any(|&player| config.is_local_player(player)) {
         let selection_mode = if keys.pressed(KeyCode::LControl) {
             SelectionMode::AddToggle
         } else {
@@ -166,7 +293,8 @@ fn left_click_handler(
         };
         select_events.send(event);
     } else {
-        draft_events.send(SpawnDraftsEvent);
+        let queue = keys.pressed(KeyCode::LShift) || keys.pressed(KeyCode::RShift);
+        draft_events.send(SpawnDraftsEvent::new(queue));
     }
 }
 
@@ -176,11 +304,12 @@ fn discard_drafts(mut events: EventWriter<DiscardDraftsEvent>) {
 
 fn place_draft(
     building_type: BuildingType,
-) -> impl Fn(Res<ObjectCounter>, Res<Pointer>, EventWriter<NewDraftEvent>) {
-    move |counter: Res<ObjectCounter>,
+) -> impl Fn(Res<GameConfig>, Res<ObjectCounter>, Res<Pointer>, EventWriter<NewDraftEvent>) {
+    move |game_config: Res<GameConfig>,
+          counter: Res<ObjectCounter>,
           pointer: Res<Pointer>,
           mut events: EventWriter<NewDraftEvent>| {
-        if counter.building_count() >= PLAYER_MAX_BUILDINGS {
+        if counter.building_count(game_config.player()) >= PLAYER_MAX_BUILDINGS {
             warn!("Maximum number of buildings reached.");
             return;
         }
@@ -193,6 +322,88 @@ fn place_draft(
     }
 }
 
+fn set_stance(stance: Stance) -> impl Fn(Commands, Query<Entity, (With<Selected>, With<Stance>)>) {
+    move |mut commands: Commands, selected: Query<Entity, (With<Selected>, With<Stance>)>| {
+        for entity in selected.iter() {
+            commands.entity(entity).insert(stance);
+        }
+    }
+}
+
+type SelectedFactories<'w, 's> =
+    Query<'w, 's, (Entity, &'static Factory, &'static ProductionQueue), With<Selected>>;
+
+/// Enqueues a unit in all selected buildings which are able to produce it.
+/// The unit is moved right behind the unit in production if `prioritize` is
+/// true.
+fn enqueue(
+    unit_type: UnitType,
+    prioritize: bool,
+) -> impl Fn(
+    Res<GameConfig>,
+    Res<Stockpiles>,
+    Res<ObjectCache>,
+    SelectedFactories,
+    EventWriter<ProductionEvent>,
+) {
+    move |game_config: Res<GameConfig>,
+          stockpiles: Res<Stockpiles>,
+          cache: Res<ObjectCache>,
+          factories: SelectedFactories,
+          mut events: EventWriter<ProductionEvent>| {
+        let cost = cache
+            .get(ObjectType::Active(ActiveObjectType::Unit(unit_type)))
+            .cost();
+        if !stockpiles.can_afford(game_config.player(), cost) {
+            warn!("Not enough resources.");
+            return;
+        }
+
+        for (entity, factory, queue) in factories.iter() {
+            if !factory.produces(unit_type) {
+                continue;
+            }
+
+            events.send(ProductionEvent::new(
+                entity,
+                ProductionCommand::Enqueue(unit_type),
+            ));
+            if prioritize && queue.len() > 1 {
+                events.send(ProductionEvent::new(
+                    entity,
+                    ProductionCommand::Reorder {
+                        from: queue.len(),
+                        to: 1,
+                    },
+                ));
+            }
+        }
+    }
+}
+
+/// Cancels the last queued unit in all selected buildings.
+fn cancel_production(factories: SelectedFactories, mut events: EventWriter<ProductionEvent>) {
+    for (entity, _, queue) in factories.iter() {
+        if !queue.is_empty() {
+            events.send(ProductionEvent::new(
+                entity,
+                ProductionCommand::Cancel(queue.len() - 1),
+            ));
+        }
+    }
+}
+
+/// Cancels construction of all selected buildings which are under
+/// construction.
+fn cancel_construction(
+    selected: Query<Entity, (With<Selected>, With<UnderConstruction>)>,
+    mut events: EventWriter<CancelConstructionEvent>,
+) {
+    for entity in selected.iter() {
+        events.send(CancelConstructionEvent::new(entity));
+    }
+}
+
 fn select_all(
     playable: Query<Entity, (With<Playable>, Without<Selected>)>,
     mut events: EventWriter<SelectEvent>,
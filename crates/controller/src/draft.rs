@@ -1,12 +1,22 @@
 use bevy::prelude::*;
+use de_behaviour::{Order, OrderEvent, OrderMode};
 use de_core::{
     gconfig::GameConfig,
-    objects::{BuildingType, ObjectType},
+    objects::{ActiveObjectType, BuildingType, MovableSolid, ObjectType},
+    player::Player,
+    projection::ToFlat,
     stages::GameStage,
+    state::GameState,
 };
+use de_economy::Stockpiles;
+use de_objects::ObjectCache;
 use de_spawner::{Draft, DraftBundle, SpawnBundle};
+use iyes_loopless::prelude::*;
 
-use crate::pointer::{Pointer, PointerLabels};
+use crate::{
+    pointer::{Pointer, PointerLabels},
+    selection::Selected,
+};
 
 pub(crate) struct DraftPlugin;
 
@@ -18,7 +28,16 @@ impl Plugin for DraftPlugin {
             .add_system_set_to_stage(
                 GameStage::Input,
                 SystemSet::new()
-                    .with_system(spawn.label(DraftLabels::Spawn))
+                    .with_system(
+                        spawn
+                            .run_in_state(GameState::Playing)
+                            .label(DraftLabels::Spawn),
+                    )
+                    .with_system(
+                        queue
+                            .run_in_state(GameState::Playing)
+                            .label(DraftLabels::Spawn),
+                    )
                     .with_system(new_drafts.label(DraftLabels::New))
                     .with_system(discard_drafts.label(DraftLabels::Discard))
                     .with_system(move_drafts.after(PointerLabels::Update)),
@@ -33,7 +52,10 @@ pub(crate) enum DraftLabels {
     Discard,
 }
 
-pub(crate) struct SpawnDraftsEvent;
+/// Send this event to place drafts of the local player.
+pub(crate) struct SpawnDraftsEvent {
+    queue: bool,
+}
 
 pub(crate) struct NewDraftEvent {
     point: Vec3,
@@ -42,6 +64,23 @@ pub(crate) struct NewDraftEvent {
 
 pub(crate) struct DiscardDraftsEvent;
 
+impl SpawnDraftsEvent {
+    /// Creates a new event.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - if true and some units are selected, the drafts are not
+    ///   placed right away but queued as build orders of the selected units,
+    ///   see [`Order::Build`].
+    pub(crate) fn new(queue: bool) -> Self {
+        Self { queue }
+    }
+
+    fn queue(&self) -> bool {
+        self.queue
+    }
+}
+
 impl NewDraftEvent {
     pub(crate) fn new(point: Vec3, building_type: BuildingType) -> Self {
         Self {
@@ -63,64 +102,134 @@ fn spawn(
     mut commands: Commands,
     game_config: Res<GameConfig>,
     mut events: EventReader<SpawnDraftsEvent>,
-    drafts: Query<(Entity, &Transform, &ObjectType, &Draft)>,
+    mut stockpiles: ResMut<Stockpiles>,
+    cache: Res<ObjectCache>,
+    drafts: Query<(Entity, &Transform, &ObjectType, &Draft, &Player)>,
+    selected: Query<(), (With<Selected>, With<MovableSolid>)>,
 ) {
-    if events.iter().count() == 0 {
+    let queue = match events.iter().last() {
+        Some(event) => event.queue(),
+        None => return,
+    };
+    // Drafts are handled by queue() in this case.
+    if queue && !selected.is_empty() {
         return;
     }
 
-    for (entity, &transform, &object_type, draft) in drafts.iter() {
-        if draft.allowed() {
+    for (entity, &transform, &object_type, draft, &player) in drafts.iter() {
+        if game_config.is_local_player(player) && draft.allowed() {
+            // The stockpile might have changed since the draft was last
+            // updated.
+            if !stockpiles.spend(player, cache.get(object_type).cost()) {
+                continue;
+            }
+
             commands.entity(entity).despawn_recursive();
             commands
-                .spawn_bundle(SpawnBundle::new(object_type, transform))
+                .spawn_bundle(SpawnBundle::construction(object_type, transform))
                 .insert(game_config.player());
         }
     }
 }
 
+/// Turns allowed drafts of the local player into build orders queued to the
+/// nearest selected unit.
+fn queue(
+    mut commands: Commands,
+    game_config: Res<GameConfig>,
+    mut events: EventReader<SpawnDraftsEvent>,
+    mut orders: EventWriter<OrderEvent>,
+    drafts: Query<(Entity, &Transform, &ObjectType, &Draft, &Player)>,
+    selected: Query<(Entity, &Transform), (With<Selected>, With<MovableSolid>)>,
+) {
+    if !events.iter().last().map_or(false, |event| event.queue()) {
+        return;
+    }
+
+    for (entity, transform, &object_type, draft, &player) in drafts.iter() {
+        if !game_config.is_local_player(player) || !draft.placeable() {
+            continue;
+        }
+
+        let building_type = match object_type {
+            ObjectType::Active(ActiveObjectType::Building(building_type)) => building_type,
+            _ => continue,
+        };
+
+        let location = transform.translation.to_flat();
+        let builder = selected.iter().min_by(|(_, a), (_, b)| {
+            a.translation
+                .to_flat()
+                .distance_squared(location)
+                .total_cmp(&b.translation.to_flat().distance_squared(location))
+        });
+        if let Some((builder, _)) = builder {
+            commands.entity(entity).despawn_recursive();
+            orders.send(OrderEvent::new(
+                builder,
+                Order::Build(building_type, location),
+                OrderMode::Append,
+            ));
+        }
+    }
+}
+
 fn new_drafts(
     mut commands: Commands,
+    game_config: Res<GameConfig>,
     mut events: EventReader<NewDraftEvent>,
-    drafts: Query<Entity, With<Draft>>,
+    drafts: Query<(Entity, &Player), With<Draft>>,
 ) {
     let event = match events.iter().last() {
         Some(event) => event,
         None => return,
     };
 
-    for entity in drafts.iter() {
-        commands.entity(entity).despawn_recursive();
+    for (entity, &player) in drafts.iter() {
+        if game_config.is_local_player(player) {
+            commands.entity(entity).despawn_recursive();
+        }
     }
 
-    commands.spawn_bundle(DraftBundle::new(
-        event.building_type(),
-        Transform {
-            translation: event.point(),
-            ..Default::default()
-        },
-    ));
+    commands
+        .spawn_bundle(DraftBundle::new(
+            event.building_type(),
+            Transform {
+                translation: event.point(),
+                ..Default::default()
+            },
+        ))
+        .insert(game_config.player());
 }
 
 fn discard_drafts(
     mut commands: Commands,
+    game_config: Res<GameConfig>,
     mut events: EventReader<DiscardDraftsEvent>,
-    drafts: Query<Entity, With<Draft>>,
+    drafts: Query<(Entity, &Player), With<Draft>>,
 ) {
     if events.iter().count() == 0 {
         return;
     }
-    for entity in drafts.iter() {
-        commands.entity(entity).despawn_recursive();
+    for (entity, &player) in drafts.iter() {
+        if game_config.is_local_player(player) {
+            commands.entity(entity).despawn_recursive();
+        }
     }
 }
 
-fn move_drafts(pointer: Res<Pointer>, mut drafts: Query<&mut Transform, With<Draft>>) {
+fn move_drafts(
+    game_config: Res<GameConfig>,
+    pointer: Res<Pointer>,
+    mut drafts: Query<(&mut Transform, &Player), With<Draft>>,
+) {
     let point = match pointer.terrain_point() {
         Some(point) => point,
         None => return,
     };
-    for mut transform in drafts.iter_mut() {
-        transform.translation = point;
+    for (mut transform, &player) in drafts.iter_mut() {
+        if game_config.is_local_player(player) {
+            transform.translation = point;
+        }
     }
 }
@@ -3,6 +3,7 @@ use de_core::{stages::GameStage, state::GameState};
 use de_index::SpatialQuery;
 use de_signs::UpdateBarVisibilityEvent;
 use de_terrain::TerrainCollider;
+use de_vision::Unseen;
 use glam::{Vec2, Vec3};
 use iyes_loopless::prelude::*;
 use parry3d::query::Ray;
@@ -45,7 +46,8 @@ pub(crate) struct Pointer {
 }
 
 impl Pointer {
-    /// Pointed to entity or None if mouse is not over any entity.
+    /// Pointed to entity or None if mouse is not over any entity. Objects not
+    /// visible by the local player are ignored.
     pub(crate) fn entity(&self) -> Option<Entity> {
         self.entity
     }
@@ -93,7 +95,7 @@ fn mouse_move_handler(
     mut resource: ResMut<Pointer>,
     mouse: Res<MousePosition>,
     screen_ray: ScreenRay,
-    entities: SpatialQuery<()>,
+    entities: SpatialQuery<(), Without<Unseen>>,
     terrain: TerrainCollider,
 ) {
     let ray = mouse.ndc().map(|cursor| screen_ray.ray(cursor));
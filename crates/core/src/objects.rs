@@ -46,12 +46,14 @@ impl fmt::Display for ObjectType {
 #[derive(Copy, Clone, Debug, Component, Serialize, Deserialize, PartialEq, Eq, Enum)]
 pub enum InactiveObjectType {
     Tree,
+    Deposit,
 }
 
 impl fmt::Display for InactiveObjectType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Tree => write!(f, "Tree"),
+            Self::Deposit => write!(f, "Deposit"),
         }
     }
 }
@@ -75,6 +77,7 @@ impl fmt::Display for ActiveObjectType {
 pub enum BuildingType {
     Base,
     PowerHub,
+    Turret,
 }
 
 impl fmt::Display for BuildingType {
@@ -82,6 +85,7 @@ impl fmt::Display for BuildingType {
         match self {
             Self::Base => write!(f, "Base"),
             Self::PowerHub => write!(f, "Power Hub"),
+            Self::Turret => write!(f, "Turret"),
         }
     }
 }
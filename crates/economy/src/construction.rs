@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use de_core::{objects::ObjectType, player::Player, stages::GameStage, state::GameState};
+use de_objects::ObjectCache;
+use de_spawner::UnderConstruction;
+use iyes_loopless::prelude::*;
+
+use crate::stockpile::Stockpiles;
+
+pub(crate) struct ConstructionPlugin;
+
+impl Plugin for ConstructionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CancelConstructionEvent>()
+            .add_system_to_stage(
+                GameStage::PreUpdate,
+                cancel.run_in_state(GameState::Playing),
+            );
+    }
+}
+
+/// Send this event to cancel construction of a building. The building is
+/// despawned and its cost is refunded to its owner. The event is ignored if
+/// the building is not under construction.
+pub struct CancelConstructionEvent {
+    building: Entity,
+}
+
+impl CancelConstructionEvent {
+    pub fn new(building: Entity) -> Self {
+        Self { building }
+    }
+
+    fn building(&self) -> Entity {
+        self.building
+    }
+}
+
+fn cancel(
+    mut commands: Commands,
+    mut events: EventReader<CancelConstructionEvent>,
+    mut stockpiles: ResMut<Stockpiles>,
+    cache: Res<ObjectCache>,
+    buildings: Query<(&Player, &ObjectType), With<UnderConstruction>>,
+) {
+    for event in events.iter() {
+        if let Ok((&player, &object_type)) = buildings.get(event.building()) {
+            commands.entity(event.building()).despawn_recursive();
+            stockpiles.add(player, cache.get(object_type).cost());
+        }
+    }
+}
@@ -0,0 +1,135 @@
+#![allow(clippy::forget_non_drop)] // Needed because of #[derive(Bundle)]
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use de_core::{
+    objects::{InactiveObjectType, ObjectType},
+    player::Player,
+    projection::ToFlat,
+    stages::GameStage,
+    state::GameState,
+};
+use de_objects::Extractor;
+use de_spawner::SpawnBundle;
+use iyes_loopless::prelude::*;
+
+use crate::{power::PowerSupply, stockpile::Stockpiles};
+
+/// Resources are extracted this often. See [`Extractor::rate`].
+const EXTRACTION_INTERVAL: Duration = Duration::from_secs(1);
+
+pub(crate) struct DepositPlugin;
+
+impl Plugin for DepositPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(GameStage::Update, extract.run_in_state(GameState::Playing));
+    }
+}
+
+/// Bundle to spawn a resource deposit. Deposits are spawned as any other
+/// object of type [`InactiveObjectType::Deposit`], see [`SpawnBundle`].
+#[derive(Bundle)]
+pub struct DepositBundle {
+    #[bundle]
+    spawn: SpawnBundle,
+    deposit: Deposit,
+}
+
+impl DepositBundle {
+    pub fn new(amount: u32, transform: Transform) -> Self {
+        Self {
+            spawn: SpawnBundle::new(ObjectType::Inactive(InactiveObjectType::Deposit), transform),
+            deposit: Deposit::new(amount),
+        }
+    }
+}
+
+/// A deposit of resources. The deposit is despawned once all its resources
+/// are extracted.
+#[derive(Component)]
+pub struct Deposit {
+    remaining: u32,
+}
+
+impl Deposit {
+    fn new(amount: u32) -> Self {
+        Self { remaining: amount }
+    }
+
+    /// Amount of resources remaining in the deposit.
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    /// Takes up to `amount` resources from the deposit and returns the taken
+    /// amount.
+    fn take(&mut self, amount: u32) -> u32 {
+        let taken = amount.min(self.remaining);
+        self.remaining -= taken;
+        taken
+    }
+}
+
+struct ExtractionTimer(Timer);
+
+impl Default for ExtractionTimer {
+    fn default() -> Self {
+        Self(Timer::new(EXTRACTION_INTERVAL, true))
+    }
+}
+
+/// Each extractor extracts resources from the nearest non-empty deposit in
+/// its range. Extractors which consume energy extract proportionally less
+/// when they are not fully powered.
+fn extract(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut timer: Local<ExtractionTimer>,
+    mut stockpiles: ResMut<Stockpiles>,
+    extractors: Query<(&Player, &Transform, &Extractor, Option<&PowerSupply>)>,
+    mut deposits: Query<(Entity, &Transform, &mut Deposit)>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    for (&player, transform, extractor, supply) in extractors.iter() {
+        let position = transform.translation.to_flat();
+        let nearest = deposits
+            .iter_mut()
+            .filter(|(_, _, deposit)| deposit.remaining() > 0)
+            .map(|(entity, deposit_transform, deposit)| {
+                let distance = deposit_transform.translation.to_flat().distance(position);
+                (entity, distance, deposit)
+            })
+            .filter(|&(_, distance, _)| distance <= extractor.range())
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((entity, _, mut deposit)) = nearest {
+            let rate = match supply {
+                Some(supply) => (extractor.rate() as f32 * supply.efficiency()).round() as u32,
+                None => extractor.rate(),
+            };
+            stockpiles.add(player, deposit.take(rate));
+            if deposit.remaining() == 0 {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take() {
+        let mut deposit = Deposit::new(7);
+        assert_eq!(deposit.take(5), 5);
+        assert_eq!(deposit.remaining(), 2);
+        assert_eq!(deposit.take(5), 2);
+        assert_eq!(deposit.remaining(), 0);
+        assert_eq!(deposit.take(5), 0);
+    }
+}
@@ -0,0 +1,31 @@
+//! This crate implements the resource economy: resource deposits, their
+//! extraction, per-player stockpiles of resources which are spent on
+//! construction and production of objects (and refunded when a construction
+//! is cancelled) and the power grid.
+
+use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
+pub use construction::CancelConstructionEvent;
+use construction::ConstructionPlugin;
+use deposit::DepositPlugin;
+pub use deposit::{Deposit, DepositBundle};
+use power::PowerPlugin;
+pub use power::PowerSupply;
+use stockpile::StockpilePlugin;
+pub use stockpile::{Stockpiles, INITIAL_STOCKPILE};
+
+mod construction;
+mod deposit;
+mod power;
+mod stockpile;
+
+pub struct EconomyPluginGroup;
+
+impl PluginGroup for EconomyPluginGroup {
+    fn build(&mut self, group: &mut PluginGroupBuilder) {
+        group
+            .add(StockpilePlugin)
+            .add(DepositPlugin)
+            .add(PowerPlugin)
+            .add(ConstructionPlugin);
+    }
+}
@@ -0,0 +1,131 @@
+use ahash::{AHashMap, AHashSet};
+use bevy::prelude::*;
+use de_core::{player::Player, projection::ToFlat, stages::GameStage, state::GameState};
+use de_objects::{PowerConsumer, PowerSource};
+use iyes_loopless::prelude::*;
+
+pub(crate) struct PowerPlugin;
+
+impl Plugin for PowerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            GameStage::PreUpdate,
+            distribute.run_in_state(GameState::Playing),
+        );
+    }
+}
+
+/// Power supply of an object which consumes energy. The component is
+/// automatically inserted to and updated on all entities with a
+/// [`PowerConsumer`] component.
+#[derive(Component, Copy, Clone, Debug, PartialEq)]
+pub struct PowerSupply {
+    efficiency: f32,
+}
+
+impl PowerSupply {
+    /// Returns a number between 0 and 1 (inclusive). It is 0 for objects
+    /// out of range of all power sources of their player and 1 for objects
+    /// of players who produce at least as much energy as they consume.
+    ///
+    /// Activity of the objects (e.g. charging of cannons) is slowed down
+    /// proportionally.
+    pub fn efficiency(&self) -> f32 {
+        self.efficiency
+    }
+}
+
+/// Energy produced and consumed by a single player.
+#[derive(Default)]
+struct Balance {
+    production: u32,
+    consumption: u32,
+}
+
+impl Balance {
+    fn efficiency(&self) -> f32 {
+        if self.consumption <= self.production {
+            1.
+        } else {
+            self.production as f32 / self.consumption as f32
+        }
+    }
+}
+
+/// Connects energy consumers to power sources in range and updates their
+/// power supply. Only connected consumers consume energy.
+fn distribute(
+    mut commands: Commands,
+    sources: Query<(&Player, &Transform, &PowerSource)>,
+    mut consumers: Query<(
+        Entity,
+        &Player,
+        &Transform,
+        &PowerConsumer,
+        Option<&mut PowerSupply>,
+    )>,
+) {
+    let mut balances: AHashMap<Player, Balance> = AHashMap::new();
+    for (&player, _, source) in sources.iter() {
+        balances.entry(player).or_default().production += source.production();
+    }
+
+    let mut connected = AHashSet::new();
+    for (entity, &player, transform, consumer, _) in consumers.iter() {
+        let position = transform.translation.to_flat();
+        let in_range = sources.iter().any(|(&owner, source_transform, source)| {
+            owner == player
+                && source_transform.translation.to_flat().distance(position) <= source.range()
+        });
+        if in_range {
+            connected.insert(entity);
+            balances.entry(player).or_default().consumption += consumer.consumption();
+        }
+    }
+
+    for (entity, player, _, _, supply) in consumers.iter_mut() {
+        let efficiency = if connected.contains(&entity) {
+            balances[player].efficiency()
+        } else {
+            0.
+        };
+
+        match supply {
+            Some(mut supply) => {
+                // Access the component mutably only when really needed for
+                // optimal Bevy change detection.
+                if supply.efficiency != efficiency {
+                    supply.efficiency = efficiency;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(PowerSupply { efficiency });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance() {
+        assert_eq!(Balance::default().efficiency(), 1.);
+        let balance = Balance {
+            production: 30,
+            consumption: 20,
+        };
+        assert_eq!(balance.efficiency(), 1.);
+        let balance = Balance {
+            production: 15,
+            consumption: 20,
+        };
+        assert_eq!(balance.efficiency(), 0.75);
+        let balance = Balance {
+            production: 0,
+            consumption: 20,
+        };
+        assert_eq!(balance.efficiency(), 0.);
+    }
+}
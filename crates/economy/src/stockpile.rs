@@ -0,0 +1,116 @@
+use ahash::AHashMap;
+use bevy::prelude::*;
+use de_core::{objects::ObjectType, player::Player, stages::GameStage, state::GameState};
+use de_objects::ObjectCache;
+use de_spawner::Draft;
+use iyes_loopless::prelude::*;
+
+/// Amount of resources each player starts with.
+pub const INITIAL_STOCKPILE: u32 = 1000;
+
+pub(crate) struct StockpilePlugin;
+
+impl Plugin for StockpilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Stockpiles>()
+            .add_enter_system(GameState::Playing, setup)
+            .add_system_to_stage(
+                GameStage::PostUpdate,
+                update_drafts.run_in_state(GameState::Playing),
+            );
+    }
+}
+
+/// Resources available to individual players.
+pub struct Stockpiles {
+    initial: u32,
+    amounts: AHashMap<Player, u32>,
+}
+
+impl Stockpiles {
+    /// # Arguments
+    ///
+    /// * `initial` - amount of resources each player starts with.
+    pub fn new(initial: u32) -> Self {
+        Self {
+            initial,
+            amounts: AHashMap::new(),
+        }
+    }
+
+    /// Returns the amount of resources available to a player.
+    pub fn amount(&self, player: Player) -> u32 {
+        self.amounts.get(&player).copied().unwrap_or(self.initial)
+    }
+
+    /// Returns true if a player has at least `cost` resources.
+    pub fn can_afford(&self, player: Player, cost: u32) -> bool {
+        self.amount(player) >= cost
+    }
+
+    /// Spends `cost` resources of a player. Nothing is spent and false is
+    /// returned if the player cannot afford it.
+    pub fn spend(&mut self, player: Player, cost: u32) -> bool {
+        let amount = self.amount(player);
+        if amount < cost {
+            return false;
+        }
+        self.amounts.insert(player, amount - cost);
+        true
+    }
+
+    /// Adds resources to the stockpile of a player, e.g. extracted resources
+    /// or refunds.
+    pub fn add(&mut self, player: Player, amount: u32) {
+        let total = self.amount(player).saturating_add(amount);
+        self.amounts.insert(player, total);
+    }
+}
+
+impl Default for Stockpiles {
+    fn default() -> Self {
+        Self::new(INITIAL_STOCKPILE)
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.insert_resource(Stockpiles::default());
+}
+
+/// Updates whether the drafting players can afford the drafted buildings, see
+/// [`Draft::affordable`].
+fn update_drafts(
+    stockpiles: Res<Stockpiles>,
+    cache: Res<ObjectCache>,
+    mut drafts: Query<(&ObjectType, &Player, &mut Draft)>,
+) {
+    for (&object_type, &player, mut draft) in drafts.iter_mut() {
+        let affordable = stockpiles.can_afford(player, cache.get(object_type).cost());
+        if affordable != draft.affordable() {
+            // Access the component mutably only when really needed for optimal
+            // Bevy change detection.
+            draft.set_affordable(affordable);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stockpiles() {
+        let mut stockpiles = Stockpiles::new(100);
+        assert_eq!(stockpiles.amount(Player::Player1), 100);
+        assert!(stockpiles.can_afford(Player::Player1, 100));
+        assert!(!stockpiles.can_afford(Player::Player1, 101));
+
+        assert!(stockpiles.spend(Player::Player1, 60));
+        assert!(!stockpiles.spend(Player::Player1, 60));
+        assert_eq!(stockpiles.amount(Player::Player1), 40);
+        assert_eq!(stockpiles.amount(Player::Player2), 100);
+
+        stockpiles.add(Player::Player1, 25);
+        assert_eq!(stockpiles.amount(Player::Player1), 65);
+    }
+}
@@ -1,8 +1,8 @@
 use de_objects::ObjectCollider;
 use parry3d::{
     bounding_volume::{Aabb, BoundingVolume},
-    math::Isometry,
-    query::{Ray, RayCast},
+    math::{Isometry, Point},
+    query::{PointQuery, Ray, RayCast},
 };
 
 pub trait ColliderWithCache {
@@ -56,6 +56,23 @@ impl LocalCollider {
         }
     }
 
+    /// Returns distance between the collider and a point or None if the
+    /// distance is larger than `max_distance`.
+    pub(crate) fn distance_to_point(&self, point: &Point<f32>, max_distance: f32) -> Option<f32> {
+        if self.world_aabb.distance_to_local_point(point, true) > max_distance {
+            return None;
+        }
+
+        let distance = self
+            .object_collider
+            .distance_to_point(&self.position, point);
+        if distance <= max_distance {
+            Some(distance)
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn intersects(&self, rhs: &impl ColliderWithCache) -> bool {
         if self.query_aabb(rhs.world_aabb()) {
             self.object_collider
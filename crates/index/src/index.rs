@@ -13,7 +13,7 @@ use bevy::{
 };
 use parry3d::{
     bounding_volume::{Aabb, BoundingVolume},
-    math::{Isometry, Point},
+    math::{Isometry, Point, Vector},
     query::Ray,
     shape::Segment,
 };
@@ -186,6 +186,42 @@ where
         })
     }
 
+    /// Returns all entities whose shape, as indexed by systems registered by
+    /// [`super::systems::IndexPlugin`], is within a distance from a point.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - center of the queried ball.
+    ///
+    /// * `radius` - radius of the queried ball.
+    ///
+    /// * `ignore` - if not None, this entity is not included in the results.
+    pub fn query_ball(
+        &self,
+        center: &Point<f32>,
+        radius: f32,
+        ignore: Option<Entity>,
+    ) -> Vec<
+        BallEntityIntersection<
+            <<<Q as WorldQuery>::ReadOnly as WorldQueryGats<'_>>::Fetch as Fetch<'_>>::Item,
+        >,
+    > {
+        let aabb = Aabb::from_half_extents(*center, Vector::repeat(radius));
+        self.index
+            .query_aabb(&aabb)
+            .flatten()
+            .filter(|&candidate| ignore.map_or(true, |ignore| candidate != ignore))
+            .filter_map(|candidate| match self.entities.get(candidate) {
+                Ok(item) => self
+                    .index
+                    .get_collider(candidate)
+                    .distance_to_point(center, radius)
+                    .map(|distance| BallEntityIntersection::new(candidate, distance, item)),
+                Err(_) => None,
+            })
+            .collect()
+    }
+
     pub fn query_aabb<'a, 'b>(
         &'a self,
         aabb: &'b Aabb,
@@ -249,6 +285,37 @@ impl<T> PartialEq for RayEntityIntersection<T> {
 
 impl<T> Eq for RayEntityIntersection<T> {}
 
+pub struct BallEntityIntersection<T> {
+    entity: Entity,
+    distance: f32,
+    item: T,
+}
+
+impl<T> BallEntityIntersection<T> {
+    fn new(entity: Entity, distance: f32, item: T) -> Self {
+        Self {
+            entity,
+            distance,
+            item,
+        }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Distance between the ball center and the entity shape. It is zero if
+    /// the ball center lies inside the shape.
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    /// Single item (ECS world query result) associated with the entity.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+}
+
 pub struct AabbQueryResults<'w, 's, 'a, 'b, Q, F = ()>
 where
     Q: WorldQuery + Sync + Send + 'static,
@@ -422,6 +489,14 @@ mod tests {
 
         let intersection_b = collider.cast_ray(&ray, f32::INFINITY).unwrap();
         assert_eq!(intersection_b, 8.);
+
+        assert_eq!(
+            collider.distance_to_point(&Point::new(12., 0., 0.), 5.),
+            Some(2.)
+        );
+        assert!(collider
+            .distance_to_point(&Point::new(20., 0., 0.), 5.)
+            .is_none());
     }
 
     #[test]
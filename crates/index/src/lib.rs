@@ -18,7 +18,7 @@ use systems::IndexPlugin;
 
 pub use self::{
     collider::{ColliderWithCache, LocalCollider, QueryCollider},
-    index::{EntityIndex, RayEntityIntersection, SpatialQuery},
+    index::{BallEntityIntersection, EntityIndex, RayEntityIntersection, SpatialQuery},
     systems::IndexLabel,
 };
 
@@ -8,8 +8,10 @@ use de_core::{
     gconfig::GameConfig,
     log_full_error,
     objects::{ActiveObjectType, BuildingType, ObjectType},
+    projection::ToMsl,
     state::GameState,
 };
+use de_economy::DepositBundle;
 use de_map::{
     description::{InnerObject, Map},
     io::{load_map, MapLoadingError},
@@ -20,6 +22,12 @@ use futures_lite::future;
 use iyes_loopless::prelude::*;
 use iyes_progress::prelude::*;
 
+/// Maps without any resource deposits get a deposit this far (in meters) from
+/// each Base placed on the map, in the direction of the map center.
+const FALLBACK_DEPOSIT_DISTANCE: f32 = 30.;
+/// Amount of resources in each fallback deposit.
+const FALLBACK_DEPOSIT_AMOUNT: u32 = 20_000;
+
 pub(crate) struct MapLoaderPlugin;
 
 impl Plugin for MapLoaderPlugin {
@@ -94,24 +102,74 @@ fn spawn_map(
     commands.spawn_bundle(TerrainBundle::flat(map.bounds()));
 
     for object in map.objects() {
-        let mut entity_commands = commands.spawn();
-        let object_type = match object.inner() {
+        let transform = object.placement().to_transform();
+        match object.inner() {
             InnerObject::Active(object) => {
-                entity_commands.insert(object.player());
-                ObjectType::Active(object.object_type())
+                commands
+                    .spawn_bundle(SpawnBundle::new(
+                        ObjectType::Active(object.object_type()),
+                        transform,
+                    ))
+                    .insert(object.player());
+            }
+            InnerObject::Inactive(object) => {
+                commands.spawn_bundle(SpawnBundle::new(
+                    ObjectType::Inactive(object.object_type()),
+                    transform,
+                ));
+            }
+            InnerObject::Deposit(deposit) => {
+                commands.spawn_bundle(DepositBundle::new(deposit.amount(), transform));
             }
-            InnerObject::Inactive(object) => ObjectType::Inactive(object.object_type()),
-        };
-        entity_commands.insert_bundle(SpawnBundle::new(
-            object_type,
-            object.placement().to_transform(),
-        ));
+        }
+    }
+
+    for position in fallback_deposits(&map) {
+        let transform = Transform::from_translation(position.to_msl());
+        commands.spawn_bundle(DepositBundle::new(FALLBACK_DEPOSIT_AMOUNT, transform));
     }
 
     commands.insert_resource(map.bounds());
     true.into()
 }
 
+/// Returns positions of resource deposits which are added to maps without
+/// any deposits (e.g. maps created before deposits were introduced) so that
+/// players still have an income.
+fn fallback_deposits(map: &Map) -> Vec<Vec2> {
+    if map
+        .objects()
+        .iter()
+        .any(|object| matches!(object.inner(), InnerObject::Deposit(_)))
+    {
+        return Vec::new();
+    }
+
+    warn!("Map has no resource deposits, placing a deposit next to each Base.");
+
+    let bounds = map.bounds();
+    let center = (bounds.min() + bounds.max()) / 2.;
+    map.objects()
+        .iter()
+        .filter_map(|object| match object.inner() {
+            InnerObject::Active(active_object)
+                if active_object.object_type()
+                    == ActiveObjectType::Building(BuildingType::Base) =>
+            {
+                let base = object.placement().position();
+                let direction = (center - base).try_normalize().unwrap_or(Vec2::X);
+                let position = base + FALLBACK_DEPOSIT_DISTANCE * direction;
+                if bounds.contains(position) {
+                    Some(position)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 fn setup_light(commands: &mut Commands) {
     commands.insert_resource(AmbientLight {
         color: Color::WHITE,
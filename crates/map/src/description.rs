@@ -204,6 +204,11 @@ impl Object {
                 }
             }
             InnerObject::Inactive(_) => (),
+            InnerObject::Deposit(deposit) => {
+                if let Err(error) = deposit.validate() {
+                    return Err(ObjectValidationError::DepositError { source: error });
+                }
+            }
         }
 
         Ok(())
@@ -216,12 +221,15 @@ pub enum ObjectValidationError {
     PlacementError { source: PlacementValidationError },
     #[error("active object error")]
     ActiveObjectError { source: ActiveObjectValidationError },
+    #[error("deposit error")]
+    DepositError { source: DepositValidationError },
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub enum InnerObject {
     Active(ActiveObject),
     Inactive(InactiveObject),
+    Deposit(Deposit),
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -278,6 +286,39 @@ impl InactiveObject {
     }
 }
 
+/// A deposit of resources which can be extracted by objects of the players.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Deposit {
+    amount: u32,
+}
+
+impl Deposit {
+    /// # Arguments
+    ///
+    /// * `amount` - amount of resources in the deposit. It has to be
+    ///   positive.
+    pub fn new(amount: u32) -> Self {
+        Self { amount }
+    }
+
+    pub fn amount(&self) -> u32 {
+        self.amount
+    }
+
+    fn validate(&self) -> Result<(), DepositValidationError> {
+        if self.amount == 0 {
+            return Err(DepositValidationError::Empty);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DepositValidationError {
+    #[error("deposit has to contain a positive amount of resources")]
+    Empty,
+}
+
 #[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct Placement {
     position: Vec2,
@@ -344,12 +385,23 @@ mod test {
             )),
         );
         map.insert_object(object_a);
+        let deposit = Object::new(
+            map.new_placement(Vec2::new(-20., 25.), 0.),
+            InnerObject::Deposit(Deposit::new(1000)),
+        );
+        map.insert_object(deposit);
 
         map.validate().unwrap();
         assert_eq!(map.bounds(), MapBounds::new(Vec2::new(1000., 1000.)));
         assert_eq!(map.max_player(), Player::Player3);
     }
 
+    #[test]
+    fn test_deposit_validation() {
+        assert!(Deposit::new(1).validate().is_ok());
+        assert!(Deposit::new(0).validate().is_err());
+    }
+
     #[test]
     fn test_map_validation() {
         let map = Map {
@@ -366,6 +418,13 @@ mod test {
                         Player::Player2,
                     )),
                 },
+                Object {
+                    placement: Placement {
+                        position: Vec2::new(2., 0.),
+                        heading: 0.,
+                    },
+                    inner: InnerObject::Deposit(Deposit::new(100)),
+                },
                 Object {
                     placement: Placement {
                         position: Vec2::new(100., 0.),
@@ -392,7 +451,7 @@ mod test {
                 }
 
                 assert_eq!(chain.len(), 3);
-                assert_eq!(chain[0], "invalid objects[1]");
+                assert_eq!(chain[0], "invalid objects[2]");
                 assert_eq!(chain[1], "invalid object placement");
                 assert_eq!(chain[2], "position (100, 0) is out of map bounds");
             }
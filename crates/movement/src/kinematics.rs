@@ -5,9 +5,9 @@ use de_core::{objects::MovableSolid, projection::ToMsl, stages::GameStage, state
 use iyes_loopless::prelude::*;
 
 use crate::{
-    movement::{DesiredVelocity, MovementLabels, ObjectVelocity},
+    movement::{DesiredVelocity, MaxSpeed, MovementLabels, ObjectVelocity},
     repulsion::{RepulsionLables, RepulsionVelocity},
-    MAX_ACCELERATION, MAX_ANGULAR_SPEED, MAX_SPEED,
+    MAX_ACCELERATION, MAX_ANGULAR_SPEED,
 };
 
 pub(crate) struct KinematicsPlugin;
@@ -56,9 +56,9 @@ impl Kinematics {
         self.heading
     }
 
-    fn update_speed(&mut self, delta: f32) {
+    fn update_speed(&mut self, delta: f32, max_speed: f32) {
         debug_assert!(delta.is_finite());
-        self.speed = (self.speed + delta).clamp(0., MAX_SPEED);
+        self.speed = (self.speed + delta).clamp(0., max_speed);
     }
 
     fn update_heading(&mut self, delta: f32) {
@@ -91,13 +91,14 @@ fn kinematics(
     time: Res<Time>,
     mut objects: Query<(
         &DesiredVelocity<RepulsionVelocity>,
+        &MaxSpeed,
         &mut Kinematics,
         &mut ObjectVelocity,
     )>,
 ) {
     let time_delta = time.delta_seconds();
 
-    objects.par_for_each_mut(512, |(movement, mut kinematics, mut velocity)| {
+    objects.par_for_each_mut(512, |(movement, limit, mut kinematics, mut velocity)| {
         let desired_velocity = movement.velocity();
         let desired_heading = if desired_velocity == Vec2::ZERO {
             kinematics.heading()
@@ -119,7 +120,7 @@ fn kinematics(
         }
         .clamp(-max_speed_delta, max_speed_delta);
 
-        kinematics.update_speed(speed_delta);
+        kinematics.update_speed(speed_delta, limit.get());
         velocity.update(kinematics.compute_velocity(), kinematics.heading());
     });
 }
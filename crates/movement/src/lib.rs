@@ -15,7 +15,8 @@ use obstacles::ObstaclesPlugin;
 use pathing::PathingPlugin;
 use repulsion::RepulsionPlugin;
 
-/// Maximum object speed in meters per second.
+/// Maximum object speed in meters per second. Speed of individual objects
+/// may be further limited, see [`crate::movement::MaxSpeed`].
 const MAX_SPEED: f32 = 10.;
 /// Maximum object acceleration in meters per second squared.
 const MAX_ACCELERATION: f32 = 2. * MAX_SPEED;
@@ -2,15 +2,17 @@ use std::marker::PhantomData;
 
 use bevy::prelude::*;
 use de_core::{
-    objects::MovableSolid,
+    objects::{MovableSolid, ObjectType},
     projection::{ToFlat, ToMsl},
     stages::GameStage,
     state::GameState,
 };
 use de_map::size::MapBounds;
-use de_objects::EXCLUSION_OFFSET;
+use de_objects::{ObjectCache, EXCLUSION_OFFSET};
 use iyes_loopless::prelude::*;
 
+use crate::MAX_SPEED;
+
 pub(crate) struct MovementPlugin;
 
 impl Plugin for MovementPlugin {
@@ -71,6 +73,16 @@ impl<T> Default for DesiredVelocity<T> {
     }
 }
 
+/// Maximum speed (in meters per second) of a movable object.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct MaxSpeed(f32);
+
+impl MaxSpeed {
+    pub(crate) fn get(&self) -> f32 {
+        self.0
+    }
+}
+
 /// Real velocity as applied to transform of the each movable object.
 #[derive(Component, Default)]
 pub(crate) struct ObjectVelocity {
@@ -117,10 +129,18 @@ pub(crate) fn add_desired_velocity<T: 'static>(
 
 fn setup_entities(
     mut commands: Commands,
-    objects: Query<Entity, (With<MovableSolid>, Without<ObjectVelocity>)>,
+    cache: Res<ObjectCache>,
+    objects: Query<(Entity, &ObjectType), (With<MovableSolid>, Without<ObjectVelocity>)>,
 ) {
-    for entity in objects.iter() {
-        commands.entity(entity).insert(ObjectVelocity::default());
+    for (entity, &object_type) in objects.iter() {
+        let max_speed = cache
+            .get(object_type)
+            .max_speed()
+            .map_or(MAX_SPEED, |speed| speed.min(MAX_SPEED));
+        commands
+            .entity(entity)
+            .insert(ObjectVelocity::default())
+            .insert(MaxSpeed(max_speed));
     }
 }
 
@@ -1,38 +1,66 @@
+use ahash::AHashMap;
 use bevy::prelude::*;
 use de_core::{projection::ToFlat, stages::GameStage, state::GameState};
-use de_pathing::ScheduledPath;
+use de_pathing::{FormationSlot, ScheduledPath};
 use iyes_loopless::prelude::*;
 
 use crate::{
-    movement::{add_desired_velocity, DesiredVelocity},
+    movement::{add_desired_velocity, DesiredVelocity, MaxSpeed},
     MAX_ACCELERATION, MAX_SPEED,
 };
 
 const DESTINATION_ACCURACY: f32 = 0.1;
+/// Objects moving in a formation slow down once they get this far (in
+/// meters) ahead of the slowest object of their group.
+const FORMATION_SLACK: f32 = 4.;
+/// Objects moving in a formation never slow down below this fraction of
+/// the group speed so that a stuck object does not stop the whole group.
+const MIN_FORMATION_SPEED: f32 = 0.25;
 
 pub(crate) struct PathingPlugin;
 
 impl Plugin for PathingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set_to_stage(
-            GameStage::PreMovement,
-            SystemSet::new()
-                .with_system(finish_paths.run_in_state(GameState::Playing))
-                .with_system(add_desired_velocity::<PathVelocity>.run_in_state(GameState::Playing)),
-        )
-        .add_system_set_to_stage(
-            GameStage::Movement,
-            SystemSet::new().with_system(
-                follow_path
-                    .run_in_state(GameState::Playing)
-                    .label(PathingLabels::FollowPath),
-            ),
-        );
+        app.init_resource::<FormationProgress>()
+            .add_system_set_to_stage(
+                GameStage::PreMovement,
+                SystemSet::new()
+                    .with_system(finish_paths.run_in_state(GameState::Playing))
+                    .with_system(
+                        add_desired_velocity::<PathVelocity>.run_in_state(GameState::Playing),
+                    ),
+            )
+            .add_system_set_to_stage(
+                GameStage::Movement,
+                SystemSet::new()
+                    .with_system(
+                        formation_progress
+                            .run_in_state(GameState::Playing)
+                            .before(PathingLabels::FollowPath),
+                    )
+                    .with_system(
+                        follow_path
+                            .run_in_state(GameState::Playing)
+                            .label(PathingLabels::FollowPath),
+                    ),
+            );
     }
 }
 
 pub(crate) struct PathVelocity;
 
+/// Progress of each formation group along its shared path.
+#[derive(Default)]
+struct FormationProgress(AHashMap<Entity, GroupProgress>);
+
+struct GroupProgress {
+    /// Remaining path length of the furthest behind object of the group.
+    remaining: f32,
+    /// Maximum speed of the slowest object of the group. The whole group
+    /// moves at this speed.
+    speed: f32,
+}
+
 #[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemLabel)]
 pub(crate) enum PathingLabels {
     FollowPath,
@@ -44,31 +72,77 @@ fn finish_paths(
         Entity,
         &Transform,
         &ScheduledPath,
+        Option<&FormationSlot>,
         &mut DesiredVelocity<PathVelocity>,
     )>,
 ) {
-    for (entity, transform, path, mut movement) in objects.iter_mut() {
-        let remaining = path.destination().distance(transform.translation.to_flat());
+    for (entity, transform, path, slot, mut movement) in objects.iter_mut() {
+        let destination = path.destination() + slot_offset(path, slot);
+        let remaining = destination.distance(transform.translation.to_flat());
         if remaining <= DESTINATION_ACCURACY {
             movement.stop();
-            commands.entity(entity).remove::<ScheduledPath>();
+            commands
+                .entity(entity)
+                .remove::<ScheduledPath>()
+                .remove::<FormationSlot>();
         }
     }
 }
 
+fn formation_progress(
+    mut progress: ResMut<FormationProgress>,
+    objects: Query<(&Transform, &ScheduledPath, &FormationSlot, &MaxSpeed)>,
+) {
+    progress.0.clear();
+    for (transform, path, slot, max_speed) in objects.iter() {
+        let remaining =
+            path.remaining(transform.translation.to_flat() - slot_offset(path, Some(slot)));
+        let group = progress.0.entry(slot.group()).or_insert(GroupProgress {
+            remaining,
+            speed: max_speed.get(),
+        });
+        group.remaining = group.remaining.max(remaining);
+        group.speed = group.speed.min(max_speed.get());
+    }
+}
+
 fn follow_path(
+    progress: Res<FormationProgress>,
     mut objects: Query<(
         &Transform,
         &mut ScheduledPath,
+        Option<&FormationSlot>,
+        &MaxSpeed,
         &mut DesiredVelocity<PathVelocity>,
     )>,
 ) {
-    objects.par_for_each_mut(512, |(transform, mut path, mut movement)| {
+    objects.par_for_each_mut(512, |(transform, mut path, slot, limit, mut movement)| {
         let location = transform.translation.to_flat();
-        let remaining = path.destination().distance(location);
-        let advancement = path.advance(location, MAX_SPEED * 0.5);
-        let direction = (advancement - location).normalize();
-        let desired_speed = MAX_SPEED.min((2. * remaining * MAX_ACCELERATION).sqrt());
+        let offset = slot_offset(&path, slot);
+        let remaining = (path.destination() + offset).distance(location);
+
+        // The object moves along the shared path as if it was at the center
+        // of the formation.
+        let center = location - offset;
+        let speed = match slot.and_then(|slot| progress.0.get(&slot.group())) {
+            Some(group) => {
+                let lead = group.remaining - path.remaining(center);
+                group.speed
+                    * (1. - (lead - FORMATION_SLACK) / FORMATION_SLACK)
+                        .clamp(MIN_FORMATION_SPEED, 1.)
+            }
+            None => limit.get(),
+        };
+
+        let advancement = path.advance(center, MAX_SPEED * 0.5) + offset;
+        let direction = (advancement - location).normalize_or_zero();
+        let desired_speed = speed.min((2. * remaining * MAX_ACCELERATION).sqrt());
         movement.update(desired_speed * direction);
     });
 }
+
+/// Returns offset of an object from the shared path of its formation in map
+/// coordinates or zero if the object does not move in a formation.
+fn slot_offset(path: &ScheduledPath, slot: Option<&FormationSlot>) -> Vec2 {
+    slot.map_or(Vec2::ZERO, |slot| slot.oriented(path.heading()))
+}
@@ -13,10 +13,10 @@ use parry2d::{math::Isometry, na::Unit, query::PointQuery};
 use crate::{
     cache::DecayingCache,
     disc::Disc,
-    movement::{add_desired_velocity, DesiredVelocity},
+    movement::{add_desired_velocity, DesiredVelocity, MaxSpeed},
     obstacles::{MovableObstacles, ObstaclesLables, StaticObstacles},
     pathing::{PathVelocity, PathingLabels},
-    MAX_ACCELERATION, MAX_SPEED,
+    MAX_ACCELERATION,
 };
 
 const MAX_REPULSION_DISTANCE: f32 = 4.0;
@@ -269,14 +269,15 @@ fn apply(
     mut objects: Query<(
         &mut Repulsion,
         &DesiredVelocity<PathVelocity>,
+        &MaxSpeed,
         &mut DesiredVelocity<RepulsionVelocity>,
     )>,
 ) {
     objects.par_for_each_mut(
         512,
-        |(mut repulsion, path_velocity, mut repulsion_velocity)| {
+        |(mut repulsion, path_velocity, max_speed, mut repulsion_velocity)| {
             let velocity = repulsion.apply(path_velocity.velocity());
-            repulsion_velocity.update(velocity.clamp_length_max(MAX_SPEED));
+            repulsion_velocity.update(velocity.clamp_length_max(max_speed.get()));
             repulsion.clear();
         },
     );
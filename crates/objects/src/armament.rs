@@ -0,0 +1,103 @@
+use bevy::prelude::Component;
+use de_core::objects::ObjectType;
+
+use crate::cannon::Cannon;
+
+/// All weapons of an object. Each of the cannons is aimed and fired
+/// independently of the others.
+#[derive(Component, Clone)]
+pub struct Armament {
+    cannons: Vec<Cannon>,
+}
+
+impl Armament {
+    /// Creates a new armament.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `cannons` is empty.
+    pub(crate) fn new(cannons: Vec<Cannon>) -> Self {
+        debug_assert!(!cannons.is_empty());
+        Self { cannons }
+    }
+
+    pub fn cannons(&self) -> &[Cannon] {
+        self.cannons.as_slice()
+    }
+
+    pub fn cannons_mut(&mut self) -> impl Iterator<Item = &mut Cannon> {
+        self.cannons.iter_mut()
+    }
+
+    /// Returns maximum range among all cannons.
+    pub fn max_range(&self) -> f32 {
+        self.cannons
+            .iter()
+            .map(|cannon| cannon.range())
+            .max_by(f32::total_cmp)
+            .unwrap()
+    }
+
+    /// Returns maximum range among all cannons able to attack objects of type
+    /// `target`. None is returned if no cannon is able to attack such
+    /// objects.
+    pub fn range(&self, target: ObjectType) -> Option<f32> {
+        self.cannons
+            .iter()
+            .filter(|cannon| cannon.targets().matches(target))
+            .map(|cannon| cannon.range())
+            .max_by(f32::total_cmp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use de_core::objects::{ActiveObjectType, BuildingType, InactiveObjectType, UnitType};
+    use glam::Vec3;
+
+    use super::*;
+    use crate::{DamageType, TargetFilter};
+
+    #[test]
+    fn test_range() {
+        let armament = Armament::new(vec![
+            Cannon::new(
+                Vec3::ZERO,
+                20.,
+                1.,
+                DamageType::Energy,
+                TargetFilter::Any,
+                None,
+                Duration::from_secs(1),
+            ),
+            Cannon::new(
+                Vec3::ZERO,
+                50.,
+                1.,
+                DamageType::Energy,
+                TargetFilter::Buildings,
+                None,
+                Duration::from_secs(1),
+            ),
+        ]);
+
+        assert_eq!(
+            armament.range(ObjectType::Active(ActiveObjectType::Unit(
+                UnitType::Attacker
+            ))),
+            Some(20.)
+        );
+        assert_eq!(
+            armament.range(ObjectType::Active(ActiveObjectType::Building(
+                BuildingType::Base
+            ))),
+            Some(50.)
+        );
+        assert_eq!(armament.max_range(), 50.);
+        assert!(armament
+            .range(ObjectType::Inactive(InactiveObjectType::Tree))
+            .is_none());
+    }
+}
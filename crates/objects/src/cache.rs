@@ -1,4 +1,4 @@
-use std::{ops::Deref, path::PathBuf, sync::Arc};
+use std::{ops::Deref, path::PathBuf, sync::Arc, time::Duration};
 
 use bevy::{
     asset::{Asset, AssetPath, LoadState},
@@ -13,9 +13,19 @@ use iyes_loopless::prelude::*;
 use iyes_progress::prelude::*;
 
 use crate::{
+    armament::Armament,
+    cannon::Cannon,
+    damage::ArmorClass,
+    explosion::Explosion,
+    extraction::Extractor,
     ichnography::Ichnography,
     loader::{ObjectInfo, ObjectLoader},
-    LaserCannon, ObjectCollider,
+    power::{PowerConsumer, PowerSource},
+    priority::TargetPriorities,
+    production::Factory,
+    repair::{Regeneration, Repairer},
+    turret::Turret,
+    ObjectCollider,
 };
 
 pub(crate) struct CachePlugin;
@@ -68,7 +78,21 @@ pub struct CacheItem {
     scene: Handle<Scene>,
     ichnography: Ichnography,
     collider: ObjectCollider,
-    cannon: Option<LaserCannon>,
+    armament: Option<Armament>,
+    turret: Option<Turret>,
+    target_priorities: TargetPriorities,
+    explosion: Option<Explosion>,
+    sight_range: Option<f32>,
+    max_speed: Option<f32>,
+    armor: ArmorClass,
+    regeneration: Option<Regeneration>,
+    repairer: Option<Repairer>,
+    extractor: Option<Extractor>,
+    power_source: Option<PowerSource>,
+    power_consumer: Option<PowerConsumer>,
+    factory: Option<Factory>,
+    build_time: Duration,
+    cost: u32,
 }
 
 impl CacheItem {
@@ -76,8 +100,87 @@ impl CacheItem {
         self.scene.clone()
     }
 
-    pub fn cannon(&self) -> Option<&LaserCannon> {
-        self.cannon.as_ref()
+    /// Returns armament of the object or None if the object has no
+    /// cannons.
+    pub fn armament(&self) -> Option<&Armament> {
+        self.armament.as_ref()
+    }
+
+    /// Returns the turret of the object or None if cannons of the object are
+    /// fixed to its body.
+    pub fn turret(&self) -> Option<&Turret> {
+        self.turret.as_ref()
+    }
+
+    /// Returns rules according to which the object chooses its targets.
+    pub fn target_priorities(&self) -> &TargetPriorities {
+        &self.target_priorities
+    }
+
+    /// Returns the explosion caused by destruction of the object or None if
+    /// the object does not explode.
+    pub fn explosion(&self) -> Option<Explosion> {
+        self.explosion
+    }
+
+    /// Returns the distance (in meters) up to which the object sees other
+    /// objects or None if the object does not see at all.
+    pub fn sight_range(&self) -> Option<f32> {
+        self.sight_range
+    }
+
+    /// Returns maximum speed (in meters per second) of the object or None
+    /// if the object does not limit its speed on its own.
+    pub fn max_speed(&self) -> Option<f32> {
+        self.max_speed
+    }
+
+    pub fn armor(&self) -> ArmorClass {
+        self.armor
+    }
+
+    /// Returns passive health regeneration of the object or None if the
+    /// object does not regenerate.
+    pub fn regeneration(&self) -> Option<Regeneration> {
+        self.regeneration
+    }
+
+    /// Returns None if the object is not able to repair other objects.
+    pub fn repairer(&self) -> Option<Repairer> {
+        self.repairer
+    }
+
+    /// Returns None if the object is not able to extract resources from
+    /// deposits.
+    pub fn extractor(&self) -> Option<Extractor> {
+        self.extractor
+    }
+
+    /// Returns None if the object does not produce energy.
+    pub fn power_source(&self) -> Option<PowerSource> {
+        self.power_source
+    }
+
+    /// Returns None if the object does not consume energy, i.e. if it
+    /// functions regardless of power supply.
+    pub fn power_consumer(&self) -> Option<PowerConsumer> {
+        self.power_consumer
+    }
+
+    /// Returns None if the object is not able to produce units.
+    pub fn factory(&self) -> Option<&Factory> {
+        self.factory.as_ref()
+    }
+
+    /// Returns the time it takes to produce or construct the object.
+    pub fn build_time(&self) -> Duration {
+        self.build_time
+    }
+
+    /// Returns the amount of resources needed to construct or produce the
+    /// object.
+    pub fn cost(&self) -> u32 {
+        self.cost
     }
 
     pub(crate) fn ichnography(&self) -> &Ichnography {
@@ -101,10 +204,14 @@ impl CacheLoader {
                     => ItemLoader::from_name(server, "base"),
                 ObjectType::Active(ActiveObjectType::Building(BuildingType::PowerHub))
                     => ItemLoader::from_name(server, "powerhub"),
+                ObjectType::Active(ActiveObjectType::Building(BuildingType::Turret))
+                    => ItemLoader::from_name(server, "turret"),
                 ObjectType::Active(ActiveObjectType::Unit(UnitType::Attacker))
                     => ItemLoader::from_name(server, "attacker"),
                 ObjectType::Inactive(InactiveObjectType::Tree)
                     => ItemLoader::from_name(server, "tree"),
+                ObjectType::Inactive(InactiveObjectType::Deposit)
+                    => ItemLoader::from_name(server, "deposit"),
             },
         }
     }
@@ -149,11 +256,33 @@ impl ItemLoader {
 
     fn into_cache_item(self, objects: &Assets<ObjectInfo>) -> CacheItem {
         let object_info = objects.get(&self.object_info).unwrap();
+        let armament = if object_info.cannons().is_empty() {
+            None
+        } else {
+            Some(Armament::new(
+                object_info.cannons().iter().map(Cannon::from).collect(),
+            ))
+        };
+
         CacheItem {
             scene: self.scene,
             ichnography: Ichnography::from(object_info.footprint()),
             collider: ObjectCollider::from(object_info.shape()),
-            cannon: object_info.cannon().map(LaserCannon::from),
+            armament,
+            turret: object_info.turret().cloned(),
+            target_priorities: object_info.target_priorities().clone(),
+            explosion: object_info.explosion(),
+            sight_range: object_info.sight_range(),
+            max_speed: object_info.max_speed(),
+            armor: object_info.armor(),
+            regeneration: object_info.regeneration(),
+            repairer: object_info.repair(),
+            extractor: object_info.extraction(),
+            power_source: object_info.power(),
+            power_consumer: object_info.consumption(),
+            factory: object_info.production().cloned(),
+            build_time: Duration::from_secs_f32(object_info.build_time()),
+            cost: object_info.cost(),
         }
     }
 
@@ -1,26 +1,53 @@
 use std::{cmp::Ordering, time::Duration};
 
-use bevy::prelude::Component;
+use de_core::objects::{ActiveObjectType, ObjectType};
 use glam::Vec3;
+use serde::{Deserialize, Serialize};
 
-use crate::loader::LaserCannonInfo;
+use crate::{damage::DamageType, loader::CannonInfo, projectile::Projectile};
 
-#[derive(Component, Clone)]
-pub struct LaserCannon {
+/// A single weapon of an object. See [`crate::Armament`].
+#[derive(Clone)]
+pub struct Cannon {
     muzzle: Vec3,
     range: f32,
     damage: f32,
-    timer: LaserTimer,
+    damage_type: DamageType,
+    targets: TargetFilter,
+    projectile: Option<Projectile>,
+    timer: CannonTimer,
 }
 
-impl LaserCannon {
-    /// Returns relative position of the cannon muzzle to the object.
+impl Cannon {
+    pub(crate) fn new(
+        muzzle: Vec3,
+        range: f32,
+        damage: f32,
+        damage_type: DamageType,
+        targets: TargetFilter,
+        projectile: Option<Projectile>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            muzzle,
+            range,
+            damage,
+            damage_type,
+            targets,
+            projectile,
+            timer: CannonTimer::new(interval),
+        }
+    }
+
+    /// Returns relative position of the cannon muzzle to the object. The
+    /// position is rotated together with the object, or with its turret if
+    /// the object has one.
     pub fn muzzle(&self) -> Vec3 {
         self.muzzle
     }
 
-    /// Maximum range of the laser in meters. Objects further than this cannot
-    /// be hit.
+    /// Maximum range of the cannon in meters. Objects further than this cannot
+    /// be targeted.
     pub fn range(&self) -> f32 {
         self.range
     }
@@ -30,35 +57,78 @@ impl LaserCannon {
         self.damage
     }
 
-    pub fn timer(&self) -> &LaserTimer {
+    pub fn damage_type(&self) -> DamageType {
+        self.damage_type
+    }
+
+    /// Returns restriction on objects which can be attacked by the cannon.
+    pub fn targets(&self) -> TargetFilter {
+        self.targets
+    }
+
+    /// Returns properties of fired projectiles or None if the cannon is a
+    /// laser cannon, i.e. it hits instantly.
+    pub fn projectile(&self) -> Option<Projectile> {
+        self.projectile
+    }
+
+    pub fn timer(&self) -> &CannonTimer {
         &self.timer
     }
 
-    pub fn timer_mut(&mut self) -> &mut LaserTimer {
+    pub fn timer_mut(&mut self) -> &mut CannonTimer {
         &mut self.timer
     }
 }
 
-/// Timer of a laser cannon. It is used to keep track of needed cannon charging
+/// Restriction on types of objects a cannon is able to attack.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TargetFilter {
+    /// The cannon can attack any active object.
+    #[default]
+    Any,
+    /// The cannon can attack units only.
+    Units,
+    /// The cannon can attack buildings only.
+    Buildings,
+}
+
+impl TargetFilter {
+    /// Returns true if an object of type `object_type` can be attacked.
+    pub fn matches(self, object_type: ObjectType) -> bool {
+        match (self, object_type) {
+            (_, ObjectType::Inactive(_)) => false,
+            (Self::Any, ObjectType::Active(_)) => true,
+            (Self::Units, ObjectType::Active(active)) => {
+                matches!(active, ActiveObjectType::Unit(_))
+            }
+            (Self::Buildings, ObjectType::Active(active)) => {
+                matches!(active, ActiveObjectType::Building(_))
+            }
+        }
+    }
+}
+
+/// Timer of a cannon. It is used to keep track of needed cannon charging
 /// time.
 ///
-/// A laser cannon cannot fire immediately after it is activated, but takes
+/// A cannon cannot fire immediately after it is activated, but takes
 /// time to charge. After firing, it has to (re)charge. It has to recharge
 /// after it any (re)activation.
 ///
-/// LaserTimer implements total ordering based on elapsed time.
+/// CannonTimer implements total ordering based on elapsed time.
 #[derive(Eq, Clone)]
-pub struct LaserTimer {
+pub struct CannonTimer {
     interval: Duration,
     elapsed: Duration,
 }
 
-impl LaserTimer {
+impl CannonTimer {
     /// Returns a new timer.
     ///
     /// # Arguments
     ///
-    /// * `interval` - time it takes to (re)charge the laser cannon.
+    /// * `interval` - time it takes to (re)charge the cannon.
     fn new(interval: Duration) -> Self {
         Self {
             interval,
@@ -80,13 +150,13 @@ impl LaserTimer {
     /// Returns true if the cannon is ready to fire and updates the timer for
     /// (re)charging.
     ///
-    /// It is assumed that the laser cannon is fired after this method returns
+    /// It is assumed that the cannon is fired after this method returns
     /// true.
     ///
     /// The timer keeps track of any extra time beyond the time to (re)charge
     /// so that its function is not dependent on update rate. However, update
     /// interval (time between to successive calls to this method) must be
-    /// smaller or equal to laser charging interval.
+    /// smaller or equal to cannon charging interval.
     pub fn check_and_update(&mut self) -> bool {
         if self.elapsed >= self.interval {
             self.elapsed -= self.interval;
@@ -97,32 +167,35 @@ impl LaserTimer {
     }
 }
 
-impl Ord for LaserTimer {
+impl Ord for CannonTimer {
     fn cmp(&self, other: &Self) -> Ordering {
         self.elapsed.cmp(&other.elapsed)
     }
 }
 
-impl PartialOrd for LaserTimer {
+impl PartialOrd for CannonTimer {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl PartialEq for LaserTimer {
+impl PartialEq for CannonTimer {
     fn eq(&self, other: &Self) -> bool {
         self.elapsed == other.elapsed && self.interval == other.interval
     }
 }
 
-impl From<&LaserCannonInfo> for LaserCannon {
-    fn from(info: &LaserCannonInfo) -> Self {
-        Self {
-            muzzle: Vec3::from_slice(info.muzzle().as_slice()),
-            range: info.range(),
-            damage: info.damage(),
-            timer: LaserTimer::new(Duration::from_secs_f32(info.recharge_interval())),
-        }
+impl From<&CannonInfo> for Cannon {
+    fn from(info: &CannonInfo) -> Self {
+        Self::new(
+            Vec3::from_slice(info.muzzle().as_slice()),
+            info.range(),
+            info.damage(),
+            info.damage_type(),
+            info.targets(),
+            info.projectile(),
+            Duration::from_secs_f32(info.recharge_interval()),
+        )
     }
 }
 
@@ -130,11 +203,13 @@ impl From<&LaserCannonInfo> for LaserCannon {
 mod tests {
     use std::cmp::Ordering;
 
+    use de_core::objects::{BuildingType, InactiveObjectType, UnitType};
+
     use super::*;
 
     #[test]
     fn test_timer() {
-        let mut timer = LaserTimer::new(Duration::from_secs_f32(2.5));
+        let mut timer = CannonTimer::new(Duration::from_secs_f32(2.5));
 
         assert!(!timer.check_and_update());
         assert!(!timer.check_and_update());
@@ -154,11 +229,11 @@ mod tests {
 
     #[test]
     fn test_timer_ordering() {
-        let mut a = LaserTimer::new(Duration::from_secs(2));
+        let mut a = CannonTimer::new(Duration::from_secs(2));
         a.tick(Duration::from_secs(3));
-        let mut b = LaserTimer::new(Duration::from_secs(3));
+        let mut b = CannonTimer::new(Duration::from_secs(3));
         b.tick(Duration::from_secs(3));
-        let mut c = LaserTimer::new(Duration::from_secs(0));
+        let mut c = CannonTimer::new(Duration::from_secs(0));
         c.tick(Duration::from_secs(10));
 
         assert!(a.cmp(&b) == Ordering::Equal);
@@ -168,4 +243,19 @@ mod tests {
         assert!(b.cmp(&c) == Ordering::Less);
         assert!(c.cmp(&b) == Ordering::Greater);
     }
+
+    #[test]
+    fn test_target_filter() {
+        let unit = ObjectType::Active(ActiveObjectType::Unit(UnitType::Attacker));
+        let building = ObjectType::Active(ActiveObjectType::Building(BuildingType::Base));
+        let tree = ObjectType::Inactive(InactiveObjectType::Tree);
+
+        assert!(TargetFilter::Any.matches(unit));
+        assert!(TargetFilter::Any.matches(building));
+        assert!(!TargetFilter::Any.matches(tree));
+        assert!(TargetFilter::Units.matches(unit));
+        assert!(!TargetFilter::Units.matches(building));
+        assert!(!TargetFilter::Buildings.matches(unit));
+        assert!(TargetFilter::Buildings.matches(building));
+    }
 }
@@ -38,6 +38,12 @@ impl ObjectCollider {
         self.shape.cast_ray(position, ray, max_toi, true)
     }
 
+    /// Returns distance between a point and the collider. Zero is returned if
+    /// the point lies inside the collider.
+    pub fn distance_to_point(&self, position: &Isometry<f32>, point: &Point<f32>) -> f32 {
+        self.shape.distance_to_point(position, point, true)
+    }
+
     pub fn intersects(
         &self,
         position: &Isometry<f32>,
@@ -94,7 +100,7 @@ impl From<&TriMeshShape> for ObjectCollider {
 #[cfg(test)]
 mod tests {
     use parry3d::{
-        math::{Isometry, Vector},
+        math::{Isometry, Point, Vector},
         shape::{Cuboid, TriMesh, TriMeshFlags},
     };
 
@@ -129,6 +135,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_distance_to_point() {
+        let collider = collider(1.);
+        let position = Isometry::translation(10., 0., 0.);
+
+        assert_eq!(
+            collider.distance_to_point(&position, &Point::new(10.5, 0., 0.)),
+            0.
+        );
+        assert_eq!(
+            collider.distance_to_point(&position, &Point::new(14., 0., 0.)),
+            3.
+        );
+        assert_eq!(
+            collider.distance_to_point(&position, &Point::new(10., -3., 0.)),
+            2.
+        );
+    }
+
     fn collider(size: f32) -> ObjectCollider {
         let cube = Cuboid::new(Vector::new(size, size, size));
         let (vertices, indices) = cube.to_trimesh();
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use enum_map::{enum_map, Enum, EnumMap};
+use serde::{Deserialize, Serialize};
+
+pub(crate) struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DamageMultipliers>();
+    }
+}
+
+/// Type of damage dealt by a weapon.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Enum)]
+pub enum DamageType {
+    #[default]
+    Energy,
+    Kinetic,
+    /// All explosions deal this type of damage.
+    Explosive,
+}
+
+/// Armor class of an object. It determines how much damage of a particular
+/// type is dealt to the object, see [`DamageMultipliers`].
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Enum)]
+pub enum ArmorClass {
+    #[default]
+    Light,
+    Heavy,
+    Structure,
+}
+
+/// A table of multipliers applied to damage based on damage type and armor
+/// class of the damaged object.
+pub struct DamageMultipliers {
+    multipliers: EnumMap<DamageType, EnumMap<ArmorClass, f32>>,
+}
+
+impl DamageMultipliers {
+    /// Creates a new multiplier table.
+    ///
+    /// # Panics
+    ///
+    /// May panic if any of the multipliers is not a non-negative finite
+    /// number.
+    pub fn new(multipliers: EnumMap<DamageType, EnumMap<ArmorClass, f32>>) -> Self {
+        debug_assert!(multipliers
+            .values()
+            .flat_map(|row| row.values())
+            .all(|m| m.is_finite() && *m >= 0.));
+        Self { multipliers }
+    }
+
+    pub fn multiplier(&self, damage_type: DamageType, armor: ArmorClass) -> f32 {
+        self.multipliers[damage_type][armor]
+    }
+
+    /// Returns damage dealt to an object with armor class `armor` by raw
+    /// damage `damage` of type `damage_type`.
+    pub fn apply(&self, damage: f32, damage_type: DamageType, armor: ArmorClass) -> f32 {
+        self.multiplier(damage_type, armor) * damage
+    }
+}
+
+impl Default for DamageMultipliers {
+    fn default() -> Self {
+        Self::new(enum_map! {
+            DamageType::Energy => enum_map! {
+                ArmorClass::Light => 1.,
+                ArmorClass::Heavy => 1.25,
+                ArmorClass::Structure => 0.75,
+            },
+            DamageType::Kinetic => enum_map! {
+                ArmorClass::Light => 1.25,
+                ArmorClass::Heavy => 0.75,
+                ArmorClass::Structure => 1.,
+            },
+            DamageType::Explosive => enum_map! {
+                ArmorClass::Light => 0.75,
+                ArmorClass::Heavy => 1.,
+                ArmorClass::Structure => 1.5,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multipliers() {
+        let multipliers = DamageMultipliers::default();
+        assert_eq!(
+            multipliers.multiplier(DamageType::Energy, ArmorClass::Light),
+            1.
+        );
+        assert_eq!(
+            multipliers.apply(10., DamageType::Kinetic, ArmorClass::Heavy),
+            7.5
+        );
+        assert_eq!(
+            multipliers.apply(10., DamageType::Explosive, ArmorClass::Structure),
+            15.
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+/// Properties of an explosion. Explosions damage all objects within a radius
+/// from the point of the explosion.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Explosion {
+    radius: f32,
+    damage: f32,
+    #[serde(default)]
+    falloff: Falloff,
+    #[serde(default)]
+    friendly_fire: FriendlyFire,
+}
+
+impl Explosion {
+    /// Objects further than this (in meters) from the point of the explosion
+    /// are not affected by it.
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Damage dealt to objects at the point of the explosion.
+    pub fn damage(&self) -> f32 {
+        self.damage
+    }
+
+    pub fn falloff(&self) -> Falloff {
+        self.falloff
+    }
+
+    pub fn friendly_fire(&self) -> FriendlyFire {
+        self.friendly_fire
+    }
+
+    /// Returns damage dealt to an object at a given distance from the point
+    /// of the explosion.
+    pub fn damage_at(&self, distance: f32) -> f32 {
+        self.damage * self.falloff.factor(distance, self.radius)
+    }
+}
+
+/// Dependency of explosion damage on distance from the point of the
+/// explosion.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Falloff {
+    /// Damage is constant within the whole radius.
+    Constant,
+    /// Damage linearly decreases to zero at the edge of the radius.
+    #[default]
+    Linear,
+    /// Damage quadratically decreases to zero at the edge of the radius.
+    Quadratic,
+}
+
+impl Falloff {
+    /// Returns the fraction of the full damage dealt at a distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - distance from the point of the explosion.
+    ///
+    /// * `radius` - radius of the explosion. Must be a positive number.
+    pub fn factor(self, distance: f32, radius: f32) -> f32 {
+        debug_assert!(radius > 0.);
+        if distance > radius {
+            return 0.;
+        }
+
+        let relative = 1. - distance.max(0.) / radius;
+        match self {
+            Self::Constant => 1.,
+            Self::Linear => relative,
+            Self::Quadratic => relative * relative,
+        }
+    }
+}
+
+/// Policy of explosion damage to objects of the player who caused the
+/// explosion.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FriendlyFire {
+    /// All objects within the radius are damaged.
+    #[default]
+    Allow,
+    /// Objects of the player who caused the explosion are not damaged.
+    Deny,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_falloff() {
+        assert_eq!(Falloff::Constant.factor(0., 4.), 1.);
+        assert_eq!(Falloff::Constant.factor(3., 4.), 1.);
+        assert_eq!(Falloff::Constant.factor(4.5, 4.), 0.);
+
+        assert_eq!(Falloff::Linear.factor(0., 4.), 1.);
+        assert_eq!(Falloff::Linear.factor(1., 4.), 0.75);
+        assert_eq!(Falloff::Linear.factor(4., 4.), 0.);
+        assert_eq!(Falloff::Linear.factor(5., 4.), 0.);
+
+        assert_eq!(Falloff::Quadratic.factor(0., 4.), 1.);
+        assert_eq!(Falloff::Quadratic.factor(2., 4.), 0.25);
+        assert_eq!(Falloff::Quadratic.factor(4., 4.), 0.);
+    }
+}
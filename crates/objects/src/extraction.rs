@@ -0,0 +1,23 @@
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Objects with this component extract resources from nearby deposits for
+/// their player.
+#[derive(Component, Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Extractor {
+    range: f32,
+    rate: u32,
+}
+
+impl Extractor {
+    /// Maximum distance in meters between the extractor and a deposit it
+    /// extracts from.
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    /// Amount of resources extracted per second.
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+}
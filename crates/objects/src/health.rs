@@ -27,6 +27,7 @@ impl Default for InitialHealths {
             healths: enum_map! {
                 ActiveObjectType::Building(BuildingType::Base) => Health::full(10_000.),
                 ActiveObjectType::Building(BuildingType::PowerHub) => Health::full(1000.),
+                ActiveObjectType::Building(BuildingType::Turret) => Health::full(800.),
                 ActiveObjectType::Unit(UnitType::Attacker) => Health::full(100.),
             },
         }
@@ -37,6 +38,7 @@ impl Default for InitialHealths {
 pub struct Health {
     max: f32,
     health: f32,
+    attacker: Option<Entity>,
 }
 
 impl Health {
@@ -51,9 +53,15 @@ impl Health {
         Self {
             max: health,
             health,
+            attacker: None,
         }
     }
 
+    /// Returns maximum health.
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
     /// Returns the fraction of remaining health, i.e. ratio between current
     /// health and maximum health.
     pub fn fraction(&self) -> f32 {
@@ -65,7 +73,8 @@ impl Health {
         self.health / self.max
     }
 
-    /// This method decreases health.
+    /// This method decreases health. Destroyed objects are not affected, thus
+    /// the attacker which destroyed the object remains its last attacker.
     ///
     /// # Arguments
     ///
@@ -73,16 +82,97 @@ impl Health {
     ///   decreased. This has to be a non-negative finite number or positive
     ///   infinity.
     ///
+    /// * `attacker` - the entity which caused the damage, if any. It is
+    ///   remembered as the last attacker of the object.
+    ///
     /// # Panics
     ///
     /// This method might panic if `damage` is not a non-negative finite number
     /// or positive infinity.
-    pub fn hit(&mut self, damage: f32) {
+    pub fn hit(&mut self, damage: f32, attacker: Option<Entity>) {
         debug_assert!(damage >= 0.);
+        if self.destroyed() {
+            return;
+        }
         self.health = 0f32.max(self.health - damage);
+        if attacker.is_some() {
+            self.attacker = attacker;
+        }
+    }
+
+    /// This method increases health up to the maximum health. Health of
+    /// destroyed objects is not changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - by how much is the health increased. This has to be a
+    ///   non-negative finite number.
+    ///
+    /// # Panics
+    ///
+    /// This method might panic if `amount` is not a non-negative finite
+    /// number.
+    pub fn heal(&mut self, amount: f32) {
+        debug_assert!(amount >= 0.);
+        debug_assert!(amount.is_finite());
+        if !self.destroyed() {
+            self.health = self.max.min(self.health + amount);
+        }
+    }
+
+    /// Returns true if current health is lower than maximum health.
+    pub fn damaged(&self) -> bool {
+        self.health < self.max
     }
 
     pub fn destroyed(&self) -> bool {
         self.health <= 0.
     }
+
+    /// Returns the entity which most recently damaged the object or None if
+    /// the object was never damaged by an entity. The returned entity might
+    /// no longer exist.
+    pub fn last_attacker(&self) -> Option<Entity> {
+        self.attacker
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_and_heal() {
+        let mut health = Health::full(100.);
+        assert!(!health.damaged());
+
+        health.hit(30., Some(Entity::from_raw(1)));
+        assert!(health.damaged());
+        assert_eq!(health.last_attacker(), Some(Entity::from_raw(1)));
+        assert_eq!(health.fraction(), 0.7);
+
+        health.heal(10.);
+        assert_eq!(health.fraction(), 0.8);
+        health.heal(50.);
+        assert_eq!(health.fraction(), 1.);
+        assert!(!health.damaged());
+
+        health.hit(200., None);
+        assert!(health.destroyed());
+        assert_eq!(health.last_attacker(), Some(Entity::from_raw(1)));
+        health.heal(50.);
+        assert!(health.destroyed());
+    }
+
+    #[test]
+    fn test_hit_destroyed() {
+        let mut health = Health::full(100.);
+        health.hit(150., Some(Entity::from_raw(1)));
+        assert!(health.destroyed());
+
+        health.hit(20., Some(Entity::from_raw(2)));
+        assert!(health.destroyed());
+        assert_eq!(health.fraction(), 0.);
+        assert_eq!(health.last_attacker(), Some(Entity::from_raw(1)));
+    }
 }
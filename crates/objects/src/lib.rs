@@ -1,26 +1,47 @@
 //! This crate implements functionality around map object handling, mostly
 //! object asset caching and pre-loading.
 
+pub use armament::Armament;
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
 use cache::CachePlugin;
 pub use cache::ObjectCache;
-pub use cannon::LaserCannon;
+pub use cannon::{Cannon, TargetFilter};
 pub use collider::{ColliderCache, ObjectCollider};
+use damage::DamagePlugin;
+pub use damage::{ArmorClass, DamageMultipliers, DamageType};
+pub use explosion::{Explosion, Falloff, FriendlyFire};
+pub use extraction::Extractor;
 use health::HealthPlugin;
 pub use health::{Health, InitialHealths};
 pub use ichnography::{Ichnography, IchnographyCache, EXCLUSION_OFFSET};
+pub use power::{PowerConsumer, PowerSource};
+pub use priority::{TargetCriterion, TargetPriorities};
+pub use production::Factory;
+pub use projectile::{Guidance, Projectile};
+pub use repair::{Regeneration, Repairer};
+pub use turret::Turret;
 
+mod armament;
 mod cache;
 mod cannon;
 mod collider;
+mod damage;
+mod explosion;
+mod extraction;
 mod health;
 mod ichnography;
 mod loader;
+mod power;
+mod priority;
+mod production;
+mod projectile;
+mod repair;
+mod turret;
 
 pub struct ObjectsPluginGroup;
 
 impl PluginGroup for ObjectsPluginGroup {
     fn build(&mut self, group: &mut PluginGroupBuilder) {
-        group.add(CachePlugin).add(HealthPlugin);
+        group.add(CachePlugin).add(HealthPlugin).add(DamagePlugin);
     }
 }
@@ -4,6 +4,20 @@ use bevy::{
     reflect::TypeUuid,
 };
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    cannon::TargetFilter,
+    damage::{ArmorClass, DamageType},
+    explosion::Explosion,
+    extraction::Extractor,
+    power::{PowerConsumer, PowerSource},
+    priority::TargetPriorities,
+    production::Factory,
+    projectile::{Projectile, ProjectileValidationError},
+    repair::{Regeneration, Repairer},
+    turret::Turret,
+};
 
 const OBJECT_EXTENSION: [&str; 1] = ["obj.json"];
 
@@ -12,7 +26,36 @@ const OBJECT_EXTENSION: [&str; 1] = ["obj.json"];
 pub(crate) struct ObjectInfo {
     footprint: Footprint,
     shape: TriMeshShape,
-    cannon: Option<LaserCannonInfo>,
+    #[serde(default)]
+    cannons: Vec<CannonInfo>,
+    #[serde(default)]
+    turret: Option<Turret>,
+    #[serde(default)]
+    target_priorities: TargetPriorities,
+    #[serde(default)]
+    explosion: Option<Explosion>,
+    #[serde(default)]
+    sight_range: Option<f32>,
+    #[serde(default)]
+    max_speed: Option<f32>,
+    #[serde(default)]
+    armor: ArmorClass,
+    #[serde(default)]
+    regeneration: Option<Regeneration>,
+    #[serde(default)]
+    repair: Option<Repairer>,
+    #[serde(default)]
+    extraction: Option<Extractor>,
+    #[serde(default)]
+    power: Option<PowerSource>,
+    #[serde(default)]
+    consumption: Option<PowerConsumer>,
+    #[serde(default)]
+    production: Option<Factory>,
+    #[serde(default)]
+    build_time: f32,
+    #[serde(default)]
+    cost: u32,
 }
 
 impl ObjectInfo {
@@ -24,11 +67,103 @@ impl ObjectInfo {
         &self.shape
     }
 
-    pub(crate) fn cannon(&self) -> Option<&LaserCannonInfo> {
-        self.cannon.as_ref()
+    pub(crate) fn cannons(&self) -> &[CannonInfo] {
+        self.cannons.as_slice()
+    }
+
+    pub(crate) fn turret(&self) -> Option<&Turret> {
+        self.turret.as_ref()
+    }
+
+    pub(crate) fn target_priorities(&self) -> &TargetPriorities {
+        &self.target_priorities
+    }
+
+    /// Explosion caused by destruction of the object.
+    pub(crate) fn explosion(&self) -> Option<Explosion> {
+        self.explosion
+    }
+
+    /// Sight range of the object in meters.
+    pub(crate) fn sight_range(&self) -> Option<f32> {
+        self.sight_range
+    }
+
+    /// Maximum speed of the object in meters per second.
+    pub(crate) fn max_speed(&self) -> Option<f32> {
+        self.max_speed
+    }
+
+    pub(crate) fn armor(&self) -> ArmorClass {
+        self.armor
+    }
+
+    pub(crate) fn regeneration(&self) -> Option<Regeneration> {
+        self.regeneration
+    }
+
+    pub(crate) fn repair(&self) -> Option<Repairer> {
+        self.repair
+    }
+
+    pub(crate) fn extraction(&self) -> Option<Extractor> {
+        self.extraction
+    }
+
+    pub(crate) fn power(&self) -> Option<PowerSource> {
+        self.power
+    }
+
+    pub(crate) fn consumption(&self) -> Option<PowerConsumer> {
+        self.consumption
+    }
+
+    pub(crate) fn production(&self) -> Option<&Factory> {
+        self.production.as_ref()
+    }
+
+    /// A time duration in seconds. It takes this long to produce or
+    /// construct the object.
+    pub(crate) fn build_time(&self) -> f32 {
+        self.build_time
+    }
+
+    /// Amount of resources needed to construct or produce the object.
+    pub(crate) fn cost(&self) -> u32 {
+        self.cost
+    }
+
+    fn validate(&self) -> Result<(), ObjectInfoValidationError> {
+        if let Some(max_speed) = self.max_speed {
+            // Non-positive or NaN maximum speed would break clamping of
+            // velocities.
+            if !max_speed.is_finite() || max_speed <= 0. {
+                return Err(ObjectInfoValidationError::MaxSpeed(max_speed));
+            }
+        }
+
+        for (index, cannon) in self.cannons.iter().enumerate() {
+            if let Some(projectile) = cannon.projectile() {
+                projectile
+                    .validate()
+                    .map_err(|source| ObjectInfoValidationError::Projectile { index, source })?;
+            }
+        }
+        Ok(())
     }
 }
 
+#[derive(Error, Debug)]
+pub(crate) enum ObjectInfoValidationError {
+    #[error("maximum speed has to be a positive finite number, got {0}")]
+    MaxSpeed(f32),
+    #[error("invalid projectile of cannon {index}")]
+    Projectile {
+        index: usize,
+        source: ProjectileValidationError,
+    },
+}
+
 #[derive(Serialize, Deserialize)]
 pub(crate) struct Footprint {
     convex_hull: Vec<[f32; 2]>,
@@ -57,14 +192,20 @@ impl TriMeshShape {
 }
 
 #[derive(Serialize, Deserialize)]
-pub(crate) struct LaserCannonInfo {
+pub(crate) struct CannonInfo {
     muzzle: [f32; 3],
     range: f32,
     damage: f32,
+    #[serde(default)]
+    damage_type: DamageType,
     recharge_interval: f32,
+    #[serde(default)]
+    targets: TargetFilter,
+    #[serde(default)]
+    projectile: Option<Projectile>,
 }
 
-impl LaserCannonInfo {
+impl CannonInfo {
     pub(crate) fn muzzle(&self) -> &[f32; 3] {
         &self.muzzle
     }
@@ -77,11 +218,24 @@ impl LaserCannonInfo {
         self.damage
     }
 
+    pub(crate) fn damage_type(&self) -> DamageType {
+        self.damage_type
+    }
+
     /// A time duration in seconds. The cannon takes this long to charge before
     /// firing.
     pub(crate) fn recharge_interval(&self) -> f32 {
         self.recharge_interval
     }
+
+    pub(crate) fn targets(&self) -> TargetFilter {
+        self.targets
+    }
+
+    /// Projectile properties or None in the case of a laser cannon.
+    pub(crate) fn projectile(&self) -> Option<Projectile> {
+        self.projectile
+    }
 }
 
 pub(crate) struct ObjectLoader;
@@ -95,6 +249,7 @@ impl AssetLoader for ObjectLoader {
         Box::pin(async move {
             let object_info: ObjectInfo =
                 serde_json::from_slice(bytes).context("Failed to parse object JSON")?;
+            object_info.validate().context("Invalid object JSON")?;
             load_context.set_default_asset(LoadedAsset::new(object_info));
             Ok(())
         })
@@ -104,3 +259,34 @@ impl AssetLoader for ObjectLoader {
         OBJECT_EXTENSION.as_slice()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object_info(max_speed: &str) -> ObjectInfo {
+        let json = format!(
+            r#"{{
+                "footprint": {{"convex_hull": [[0, 0], [1, 0], [0, 1]]}},
+                "shape": {{"vertices": [[0, 0, 0], [1, 0, 0], [0, 1, 0]], "indices": [[0, 1, 2]]}},
+                "max_speed": {}
+            }}"#,
+            max_speed
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(object_info("null").validate().is_ok());
+        assert!(object_info("10.5").validate().is_ok());
+        assert!(matches!(
+            object_info("0").validate(),
+            Err(ObjectInfoValidationError::MaxSpeed(_))
+        ));
+        assert!(matches!(
+            object_info("-1").validate(),
+            Err(ObjectInfoValidationError::MaxSpeed(_))
+        ));
+    }
+}
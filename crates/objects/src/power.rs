@@ -0,0 +1,38 @@
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Objects with this component produce energy for their player and power
+/// nearby buildings of the same player.
+#[derive(Component, Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PowerSource {
+    range: f32,
+    production: u32,
+}
+
+impl PowerSource {
+    /// Maximum distance in meters between the source and buildings it powers.
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    /// Amount of energy produced by the source.
+    pub fn production(&self) -> u32 {
+        self.production
+    }
+}
+
+/// Objects with this component consume energy. They function only in range
+/// of a power source of their player.
+#[derive(Component, Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct PowerConsumer {
+    consumption: u32,
+}
+
+impl PowerConsumer {
+    /// Amount of energy needed for the object to function at full
+    /// efficiency.
+    pub fn consumption(&self) -> u32 {
+        self.consumption
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// A single rule used to choose a target among several enemies in range.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TargetCriterion {
+    /// Prefer the enemy focused by the player, i.e. the enemy the player
+    /// explicitly asked all their objects to concentrate on.
+    Focus,
+    /// Prefer enemies which are able to attack (armed objects).
+    Threat,
+    /// Prefer enemy units over enemy buildings.
+    Units,
+    /// Prefer enemy buildings over enemy units.
+    Buildings,
+    /// Prefer enemies with the lowest fraction of remaining health. This
+    /// makes groups of objects concentrate their fire.
+    LowestHealth,
+    /// Prefer the nearest enemies.
+    Closest,
+}
+
+/// Ordered list of target selection rules of an object. Earlier rules take
+/// precedence, later rules are used only to break ties. The closest enemy is
+/// chosen if all the rules are tied.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct TargetPriorities(Vec<TargetCriterion>);
+
+impl TargetPriorities {
+    pub fn new(criteria: Vec<TargetCriterion>) -> Self {
+        Self(criteria)
+    }
+
+    pub fn criteria(&self) -> &[TargetCriterion] {
+        self.0.as_slice()
+    }
+}
+
+impl Default for TargetPriorities {
+    fn default() -> Self {
+        Self::new(vec![
+            TargetCriterion::Focus,
+            TargetCriterion::Threat,
+            TargetCriterion::LowestHealth,
+            TargetCriterion::Closest,
+        ])
+    }
+}
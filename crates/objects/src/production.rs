@@ -0,0 +1,21 @@
+use bevy::prelude::Component;
+use de_core::objects::UnitType;
+use serde::{Deserialize, Serialize};
+
+/// Objects with this component are able to produce units.
+#[derive(Component, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct Factory {
+    products: Vec<UnitType>,
+}
+
+impl Factory {
+    /// Types of units which the factory is able to produce.
+    pub fn products(&self) -> &[UnitType] {
+        self.products.as_slice()
+    }
+
+    pub fn produces(&self, unit_type: UnitType) -> bool {
+        self.products.contains(&unit_type)
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::explosion::Explosion;
+
+/// Properties of projectiles fired by a cannon. Unlike laser beams,
+/// projectiles travel at finite speed and they may miss a moving target.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Projectile {
+    speed: f32,
+    #[serde(default)]
+    guidance: Guidance,
+    #[serde(default)]
+    explosion: Option<Explosion>,
+}
+
+impl Projectile {
+    /// Speed of the projectile in meters per second. In the case of
+    /// [`Guidance::Ballistic`] this is horizontal speed of the projectile.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn guidance(&self) -> Guidance {
+        self.guidance
+    }
+
+    /// Returns the explosion caused by the projectile on impact or None if
+    /// the projectile damages only the hit object. Explosive projectiles deal
+    /// no direct hit damage, the hit object is damaged only by the explosion.
+    pub fn explosion(&self) -> Option<Explosion> {
+        self.explosion
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), ProjectileValidationError> {
+        if !self.speed.is_finite() || self.speed <= 0. {
+            return Err(ProjectileValidationError::Speed(self.speed));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub(crate) enum ProjectileValidationError {
+    #[error("projectile speed has to be a positive finite number, got {0}")]
+    Speed(f32),
+}
+
+/// The way a projectile flies from the cannon muzzle to its target.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Guidance {
+    /// The projectile flies along a straight line aimed at the position of
+    /// the target during firing.
+    #[default]
+    Straight,
+    /// The projectile is affected by gravity and flies along a parabolic
+    /// trajectory which ends at the position of the target during firing.
+    Ballistic,
+    /// The projectile keeps turning towards its target until it hits
+    /// something.
+    Homing,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let projectile = Projectile {
+            speed: 40.,
+            guidance: Guidance::Ballistic,
+            explosion: None,
+        };
+        assert!(projectile.validate().is_ok());
+
+        for speed in [0., -1., f32::NAN, f32::INFINITY] {
+            let projectile = Projectile {
+                speed,
+                ..projectile
+            };
+            assert!(projectile.validate().is_err());
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Passive health regeneration of an object.
+#[derive(Component, Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct Regeneration {
+    rate: f32,
+}
+
+impl Regeneration {
+    /// Health restored per second.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+}
+
+/// Objects with this component restore health of nearby objects of the same
+/// player.
+#[derive(Component, Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Repairer {
+    range: f32,
+    rate: f32,
+}
+
+impl Repairer {
+    /// Maximum distance in meters between the repairer centroid and repaired
+    /// objects.
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    /// Health restored per second to each of the repaired objects.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+}
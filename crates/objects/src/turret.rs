@@ -0,0 +1,120 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Rotating turret of an object. All cannons of the object are mounted on
+/// the turret, thus muzzle positions rotate together with it.
+///
+/// Heading of the turret is relative to the rotation of the object. Heading
+/// 0 means that the turret faces the same direction as the object, i.e.
+/// towards negative Z axis in the object's local coordinates.
+#[derive(Component, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Turret {
+    turn_rate: f32,
+    #[serde(skip)]
+    heading: f32,
+}
+
+impl Turret {
+    /// Maximum angular speed of the turret in radians per second.
+    pub fn turn_rate(&self) -> f32 {
+        self.turn_rate
+    }
+
+    /// Current heading of the turret in radians, within interval (-π, π].
+    pub fn heading(&self) -> f32 {
+        self.heading
+    }
+
+    /// Rotation of the turret relative to the object.
+    pub fn rotation(&self) -> Quat {
+        Quat::from_rotation_y(self.heading)
+    }
+
+    /// Returns heading (relative to the object) of a direction given in
+    /// local coordinates of the object. Vertical component of the direction
+    /// is ignored.
+    pub fn heading_of(direction: Vec3) -> f32 {
+        (-direction.x).atan2(-direction.z)
+    }
+
+    /// Returns true if the turret has the given heading (in radians). Unlike
+    /// [`Self::turn`], this does not modify the turret.
+    pub fn faces(&self, heading: f32) -> bool {
+        self.heading == normalize(heading)
+    }
+
+    /// Turns the turret towards a heading, taking turn rate of the turret
+    /// into account. Returns true if the turret faces the heading after the
+    /// turn.
+    ///
+    /// # Arguments
+    ///
+    /// * `heading` - target heading in radians.
+    ///
+    /// * `time_delta` - available time in seconds.
+    pub fn turn(&mut self, heading: f32, time_delta: f32) -> bool {
+        let difference = normalize(heading - self.heading);
+        let max_turn = self.turn_rate * time_delta;
+
+        if difference.abs() <= max_turn {
+            self.heading = normalize(heading);
+            true
+        } else {
+            self.heading = normalize(self.heading + max_turn.copysign(difference));
+            false
+        }
+    }
+}
+
+/// Returns the same angle within interval (-π, π].
+fn normalize(angle: f32) -> f32 {
+    let angle = angle.rem_euclid(TAU);
+    if angle > PI {
+        angle - TAU
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_heading_of() {
+        assert_relative_eq!(Turret::heading_of(Vec3::new(0., 1., -2.)), 0.);
+        assert_relative_eq!(Turret::heading_of(Vec3::new(-1., 0., 0.)), FRAC_PI_2);
+        assert_relative_eq!(Turret::heading_of(Vec3::new(1., 0., 0.)), -FRAC_PI_2);
+    }
+
+    #[test]
+    fn test_turn() {
+        let mut turret = Turret {
+            turn_rate: 1.,
+            heading: 0.,
+        };
+
+        assert!(!turret.turn(FRAC_PI_2, 1.));
+        assert_relative_eq!(turret.heading(), 1.);
+        assert!(!turret.faces(FRAC_PI_2));
+        assert!(turret.turn(FRAC_PI_2, 1.));
+        assert_relative_eq!(turret.heading(), FRAC_PI_2);
+        assert!(turret.faces(FRAC_PI_2));
+
+        // The shorter way is across ±π.
+        turret.heading = 3.;
+        assert!(!turret.turn(-3., 0.1));
+        assert_relative_eq!(turret.heading(), 3.1);
+        assert!(turret.turn(-3., 0.5));
+        assert_relative_eq!(turret.heading(), -3.);
+
+        let rotated = turret.rotation() * Vec3::NEG_Z;
+        assert_relative_eq!(Turret::heading_of(rotated), -3., epsilon = 1e-6);
+    }
+}
@@ -193,6 +193,16 @@ impl PathFinder {
         }
     }
 
+    /// Returns true if the point lies inside the accessible area, i.e. it is
+    /// inside the map and outside of all exclusion areas.
+    pub(crate) fn is_accessible<P: Into<Point<f32>>>(&self, point: P) -> bool {
+        let point: Point<f32> = point.into();
+        self.triangles
+            .locate_all_at_point(&[point.x, point.y])
+            .next()
+            .is_some()
+    }
+
     fn locate_triangle_edges(&self, point: Point<f32>) -> Vec<u32> {
         self.triangles
             .locate_all_at_point(&[point.x, point.y])
@@ -461,6 +471,10 @@ mod tests {
         ];
 
         let finder = PathFinder::from_triangles(triangles, vec![]);
+        assert!(finder.is_accessible(Vec2::new(-0.5, 0.)));
+        assert!(finder.is_accessible(Vec2::new(2., 22.)));
+        assert!(!finder.is_accessible(Vec2::new(9., 22.)));
+        assert!(!finder.is_accessible(Vec2::new(5., 0.)));
         assert!(finder
             .find_path(
                 Point::new(-0.5, 0.),
@@ -0,0 +1,132 @@
+//! Formations in which groups of objects follow a shared path.
+
+use bevy::prelude::*;
+use glam::Vec2;
+
+/// Distance (in meters) between neighboring slots of a formation.
+const SLOT_SPACING: f32 = 6.;
+
+/// Shape of a formation of a group of objects following a shared path.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Formation {
+    /// All objects are side by side in a single row perpendicular to the
+    /// path.
+    Line,
+    /// Objects are arranged into a (roughly) square grid.
+    Box,
+    /// Objects are arranged into a V shape with a single object at its tip
+    /// leading the group.
+    Wedge,
+}
+
+impl Formation {
+    /// Returns slot offsets of a formation of `count` objects. See
+    /// [`FormationSlot::offset`].
+    ///
+    /// The returned slots are ordered from the front of the formation to its
+    /// back and the formation is centered at the origin.
+    pub(crate) fn slots(self, count: usize) -> Vec<Vec2> {
+        let mut slots: Vec<Vec2> = match self {
+            Self::Line => (0..count).map(|i| Vec2::new(i as f32, 0.)).collect(),
+            Self::Box => {
+                let columns = (count as f32).sqrt().ceil() as usize;
+                (0..count)
+                    .map(|i| Vec2::new((i % columns) as f32, -((i / columns) as f32)))
+                    .collect()
+            }
+            Self::Wedge => (0..count)
+                .map(|i| {
+                    let row = ((i + 1) / 2) as f32;
+                    let side = if i % 2 == 0 { 1. } else { -1. };
+                    Vec2::new(side * row, -row)
+                })
+                .collect(),
+        };
+
+        if count > 0 {
+            let center = slots.iter().sum::<Vec2>() / (count as f32);
+            for slot in slots.iter_mut() {
+                *slot = SLOT_SPACING * (*slot - center);
+            }
+        }
+
+        slots
+    }
+}
+
+/// Objects with this component follow their [`crate::ScheduledPath`],
+/// which is shared with the rest of their group, at an offset.
+#[derive(Component, Clone, Copy)]
+pub struct FormationSlot {
+    group: Entity,
+    offset: Vec2,
+}
+
+impl FormationSlot {
+    /// # Arguments
+    ///
+    /// * `group` - an entity identifying the group. It is one of the group
+    ///   members.
+    ///
+    /// * `offset` - see [`Self::offset`].
+    pub(crate) fn new(group: Entity, offset: Vec2) -> Self {
+        Self { group, offset }
+    }
+
+    pub fn group(&self) -> Entity {
+        self.group
+    }
+
+    /// Offset of the slot from the center of the formation. X coordinate is
+    /// perpendicular to the path (positive to the right), Y coordinate is
+    /// along the path (positive to the front).
+    pub fn offset(&self) -> Vec2 {
+        self.offset
+    }
+
+    /// Returns offset of the slot in map coordinates.
+    ///
+    /// # Arguments
+    ///
+    /// * `heading` - unit vector in the direction of the path.
+    pub fn oriented(&self, heading: Vec2) -> Vec2 {
+        let right = Vec2::new(heading.y, -heading.x);
+        self.offset.x * right + self.offset.y * heading
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slots() {
+        assert!(Formation::Box.slots(0).is_empty());
+        assert_eq!(Formation::Line.slots(1), vec![Vec2::ZERO]);
+        assert_eq!(
+            Formation::Line.slots(3),
+            vec![Vec2::new(-6., 0.), Vec2::ZERO, Vec2::new(6., 0.)]
+        );
+        assert_eq!(
+            Formation::Box.slots(4),
+            vec![
+                Vec2::new(-3., 3.),
+                Vec2::new(3., 3.),
+                Vec2::new(-3., -3.),
+                Vec2::new(3., -3.)
+            ]
+        );
+        let wedge = Formation::Wedge.slots(3);
+        assert_eq!(wedge.len(), 3);
+        assert!(wedge[0].distance(Vec2::new(0., 4.)) < 0.001);
+        assert!(wedge[1].distance(Vec2::new(-6., -2.)) < 0.001);
+        assert!(wedge[2].distance(Vec2::new(6., -2.)) < 0.001);
+    }
+
+    #[test]
+    fn test_oriented() {
+        let slot = FormationSlot::new(Entity::from_raw(1), Vec2::new(2., 3.));
+        assert_eq!(slot.oriented(Vec2::Y), Vec2::new(2., 3.));
+        assert_eq!(slot.oriented(Vec2::X), Vec2::new(3., -2.));
+    }
+}
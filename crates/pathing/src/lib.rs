@@ -3,7 +3,8 @@
 //! map.
 //!
 //! When [`UpdateEntityPath`] event is sent, the entity paths is automatically
-//! (re)planned.
+//! (re)planned. When [`UpdateGroupPath`] event is sent, a single path shared
+//! by a group of entities moving in a formation is planned.
 //!
 //!
 //! # Path Search
@@ -20,6 +21,7 @@ mod chain;
 mod dijkstra;
 mod exclusion;
 mod finder;
+mod formation;
 mod fplugin;
 mod funnel;
 mod geometry;
@@ -31,11 +33,12 @@ mod triangulation;
 mod utils;
 
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
+pub use formation::{Formation, FormationSlot};
 pub use fplugin::create_finder;
 use fplugin::FinderPlugin;
 pub use path::ScheduledPath;
 use pplugin::PathingPlugin;
-pub use pplugin::UpdateEntityPath;
+pub use pplugin::{UpdateEntityPath, UpdateGroupPath};
 pub use query::{PathQueryProps, PathTarget};
 
 pub struct PathingPluginGroup;
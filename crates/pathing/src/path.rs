@@ -31,6 +31,30 @@ impl ScheduledPath {
         self.path.waypoints()[0]
     }
 
+    /// Returns unit vector in the direction of the current path segment or
+    /// in the direction of the last path segment if only the last point
+    /// remains to be reached.
+    pub fn heading(&self) -> Vec2 {
+        let start = self.current.max(1);
+        self.path.heading(start)
+    }
+
+    /// Returns remaining length of the path as measured from the point on the
+    /// current path segment closest to a given `position`.
+    pub fn remaining(&self, position: Vec2) -> f32 {
+        if self.current == 0 {
+            return position.distance(self.destination());
+        }
+
+        let waypoints = self.path.waypoints();
+        let (projection, _) = self.projection(position);
+        projection.distance(waypoints[self.current - 1])
+            + waypoints[..self.current]
+                .windows(2)
+                .map(|pair| pair[0].distance(pair[1]))
+                .sum::<f32>()
+    }
+
     /// Advances the path schedule by a given distance and returns the
     /// corresponding point on the path.
     ///
@@ -107,6 +131,7 @@ impl ScheduledPath {
 
 /// A path on the map defined by a sequence of way points. Start and target
 /// position are included.
+#[derive(Clone)]
 pub struct Path {
     length: f32,
     waypoints: Vec<Vec2>,
@@ -151,6 +176,16 @@ impl Path {
         self.waypoints.as_slice()
     }
 
+    /// Returns unit vector in the direction of the path segment starting at
+    /// way point with index `start`.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `start` is zero or out of bounds.
+    pub(crate) fn heading(&self, start: usize) -> Vec2 {
+        (self.waypoints[start - 1] - self.waypoints[start]).normalize_or_zero()
+    }
+
     /// Returns a path shortened by `amount` from the end. Returns None
     /// `amount` is longer than the path.
     pub(crate) fn truncated(mut self, mut amount: f32) -> Option<Self> {
@@ -223,6 +258,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_schedule_heading() {
+        let mut schedule = ScheduledPath::new(Path::new(
+            7.,
+            vec![Vec2::new(4., 6.), Vec2::new(4., 1.), Vec2::new(2., 1.)],
+        ));
+        assert_eq!(schedule.heading(), Vec2::X);
+        assert_eq!(schedule.remaining(Vec2::new(3., 0.)), 6.);
+        schedule.advance(Vec2::new(4., 1.), 1.);
+        assert_eq!(schedule.heading(), Vec2::Y);
+        assert_eq!(schedule.remaining(Vec2::new(3., 2.)), 4.);
+        schedule.advance(Vec2::new(4., 6.), 1.);
+        assert_eq!(schedule.heading(), Vec2::Y);
+        assert_eq!(schedule.remaining(Vec2::new(4., 5.)), 1.);
+    }
+
     #[test]
     fn test_schedule_project() {
         let schedule = ScheduledPath::new(Path::new(
@@ -8,12 +8,18 @@ use futures_lite::future;
 use iyes_loopless::prelude::*;
 
 use crate::{
+    finder::PathFinder,
+    formation::{Formation, FormationSlot},
     fplugin::{FinderLabel, FinderRes, PathFinderUpdated},
     path::{Path, ScheduledPath},
     PathQueryProps, PathTarget,
 };
 
 const TARGET_TOLERANCE: f32 = 2.;
+/// Formation slots inaccessible at the end of a shared path are shrunk
+/// towards the center of the formation by these factors, see
+/// [`accessible_slot`].
+const SLOT_SCALES: [f32; 3] = [1., 0.5, 0.25];
 
 pub struct PathingPlugin;
 
@@ -21,6 +27,7 @@ impl Plugin for PathingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UpdatePathsState>()
             .add_event::<UpdateEntityPath>()
+            .add_event::<UpdateGroupPath>()
             .add_system_to_stage(
                 GameStage::PreMovement,
                 update_existing_paths
@@ -35,6 +42,20 @@ impl Plugin for PathingPlugin {
                     .label(PathingLabel::UpdateRequestedPaths)
                     .after(PathingLabel::UpdateExistingPaths),
             )
+            .add_system_to_stage(
+                GameStage::PreMovement,
+                update_requested_group_paths
+                    .run_in_state(GameState::Playing)
+                    .label(PathingLabel::UpdateRequestedGroupPaths)
+                    .after(PathingLabel::UpdateRequestedPaths),
+            )
+            .add_system_to_stage(
+                GameStage::PreMovement,
+                check_group_path_results
+                    .run_in_state(GameState::Playing)
+                    .before(PathingLabel::UpdateRequestedPaths)
+                    .after(PathingLabel::UpdateExistingPaths),
+            )
             .add_system_to_stage(
                 GameStage::PreMovement,
                 check_path_results
@@ -60,6 +81,7 @@ impl Plugin for PathingPlugin {
 #[derive(SystemLabel)]
 enum PathingLabel {
     UpdateRequestedPaths,
+    UpdateRequestedGroupPaths,
     UpdateExistingPaths,
 }
 
@@ -89,17 +111,102 @@ impl UpdateEntityPath {
     }
 }
 
+/// This event triggers computation of a single shortest path to a target
+/// which is shared by a group of entities. The entities follow the path in a
+/// formation, see [`FormationSlot`].
+///
+/// Each entity gets a path target at its formation slot around the target.
+pub struct UpdateGroupPath {
+    entities: Vec<Entity>,
+    target: PathTarget,
+    formation: Formation,
+}
+
+impl UpdateGroupPath {
+    /// # Arguments
+    ///
+    /// * `entities` - entities whose path should be updated / inserted.
+    ///
+    /// * `target` - desired path target of the center of the formation &
+    ///   path searching query configuration.
+    ///
+    /// * `formation` - shape of the formation.
+    pub fn new(entities: Vec<Entity>, target: PathTarget, formation: Formation) -> Self {
+        Self {
+            entities,
+            target,
+            formation,
+        }
+    }
+
+    fn entities(&self) -> &[Entity] {
+        self.entities.as_slice()
+    }
+
+    fn target(&self) -> PathTarget {
+        self.target
+    }
+
+    fn formation(&self) -> Formation {
+        self.formation
+    }
+}
+
 #[derive(Default)]
 struct UpdatePathsState {
     tasks: AHashMap<Entity, UpdatePathTask>,
+    groups: Vec<(PendingGroup, UpdatePathTask)>,
 }
 
 impl UpdatePathsState {
     fn contains(&self, entity: Entity) -> bool {
         self.tasks.contains_key(&entity)
+            || self
+                .groups
+                .iter()
+                .any(|(group, _)| group.entities.contains(&entity))
+    }
+
+    /// Spawns a new shared path computation. Previously requested paths of
+    /// the group members are abandoned.
+    fn spawn_new_group(&mut self, finder: FinderRes, group: PendingGroup, source: Vec2) {
+        for entity in group.entities.iter() {
+            self.tasks.remove(entity);
+        }
+        for (other, _) in self.groups.iter_mut() {
+            other
+                .entities
+                .retain(|entity| !group.entities.contains(entity));
+        }
+
+        let target = group.target;
+        let pool = AsyncComputeTaskPool::get();
+        let task = pool.spawn(async move { finder.find_path(source, target) });
+        self.groups.push((group, UpdatePathTask::new(task)));
+    }
+
+    fn check_group_results(&mut self) -> Vec<(PendingGroup, Option<Path>)> {
+        let mut results = Vec::new();
+        let mut i = 0;
+        while i < self.groups.len() {
+            match self.groups[i].1.check() {
+                UpdatePathState::Resolved(path) => {
+                    let (group, _) = self.groups.swap_remove(i);
+                    results.push((group, path));
+                }
+                UpdatePathState::Processing => i += 1,
+            }
+        }
+
+        results
     }
 
     fn spawn_new(&mut self, finder: FinderRes, entity: Entity, source: Vec2, target: PathTarget) {
+        // The entity no longer follows a shared path.
+        for (group, _) in self.groups.iter_mut() {
+            group.entities.retain(|&e| e != entity);
+        }
+
         let pool = AsyncComputeTaskPool::get();
         let task = pool.spawn(async move { finder.find_path(source, target) });
         self.tasks.insert(entity, UpdatePathTask::new(task));
@@ -119,6 +226,12 @@ impl UpdatePathsState {
     }
 }
 
+struct PendingGroup {
+    entities: Vec<Entity>,
+    target: PathTarget,
+    formation: Formation,
+}
+
 struct UpdatePathTask(Task<Option<Path>>);
 
 impl UpdatePathTask {
@@ -182,7 +295,10 @@ fn update_requested_paths(
 ) {
     for event in events.iter() {
         if let Ok(transform) = entities.get(event.entity()) {
-            commands.entity(event.entity()).insert(event.target());
+            commands
+                .entity(event.entity())
+                .insert(event.target())
+                .remove::<FormationSlot>();
             state.spawn_new(
                 finder.clone(),
                 event.entity(),
@@ -193,6 +309,128 @@ fn update_requested_paths(
     }
 }
 
+fn update_requested_group_paths(
+    mut commands: Commands,
+    finder: Res<FinderRes>,
+    mut state: ResMut<UpdatePathsState>,
+    mut events: EventReader<UpdateGroupPath>,
+    entities: Query<&Transform, With<MovableSolid>>,
+) {
+    for event in events.iter() {
+        let members: Vec<(Entity, Vec2)> = event
+            .entities()
+            .iter()
+            .filter_map(|&entity| {
+                entities
+                    .get(entity)
+                    .ok()
+                    .map(|transform| (entity, transform.translation.to_flat()))
+            })
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+
+        for &(entity, _) in members.iter() {
+            commands
+                .entity(entity)
+                .insert(event.target())
+                .remove::<FormationSlot>();
+        }
+
+        let centroid =
+            members.iter().map(|&(_, position)| position).sum::<Vec2>() / members.len() as f32;
+        state.spawn_new_group(
+            finder.clone(),
+            PendingGroup {
+                entities: members.iter().map(|&(entity, _)| entity).collect(),
+                target: event.target(),
+                formation: event.formation(),
+            },
+            centroid,
+        );
+    }
+}
+
+fn check_group_path_results(
+    mut commands: Commands,
+    finder: Res<FinderRes>,
+    mut state: ResMut<UpdatePathsState>,
+    entities: Query<(&Transform, &PathTarget)>,
+) {
+    for (group, path) in state.check_group_results() {
+        // Members which got a different target in the meantime are left out.
+        let members: Vec<(Entity, Vec2, PathTarget)> = group
+            .entities
+            .iter()
+            .filter_map(|&entity| {
+                entities.get(entity).ok().and_then(|(transform, &target)| {
+                    (target.location() == group.target.location())
+                        .then(|| (entity, transform.translation.to_flat(), target))
+                })
+            })
+            .collect();
+
+        let path = match path {
+            Some(path) => path,
+            None => {
+                for &(entity, _, target) in members.iter() {
+                    let mut entity_commands = commands.entity(entity);
+                    entity_commands.remove::<ScheduledPath>();
+                    if !target.permanent() {
+                        entity_commands.remove::<PathTarget>();
+                    }
+                }
+                continue;
+            }
+        };
+
+        let group_id = match members.first() {
+            Some(&(entity, _, _)) => entity,
+            None => continue,
+        };
+
+        let last = path.waypoints().len() - 1;
+        let start = path.waypoints()[last];
+        let end = path.waypoints()[0];
+        let start_heading = path.heading(last);
+        let end_heading = path.heading(1);
+
+        let mut unassigned = members;
+        for offset in group.formation.slots(unassigned.len()) {
+            let slot = accessible_slot(
+                &finder,
+                end,
+                end_heading,
+                FormationSlot::new(group_id, offset),
+            );
+
+            // Slots are assigned front to back, each to the closest
+            // remaining member.
+            let slot_start = start + slot.oriented(start_heading);
+            let index = match unassigned.iter().enumerate().min_by(|(_, a), (_, b)| {
+                a.1.distance_squared(slot_start)
+                    .total_cmp(&b.1.distance_squared(slot_start))
+            }) {
+                Some((index, _)) => index,
+                None => break,
+            };
+            let (entity, _, target) = unassigned.swap_remove(index);
+
+            let slot_target = PathTarget::new(
+                end + slot.oriented(end_heading),
+                PathQueryProps::exact(),
+                target.permanent(),
+            );
+            commands.entity(entity).insert_bundle((
+                ScheduledPath::new(path.clone()),
+                slot,
+                slot_target,
+            ));
+        }
+    }
+}
+
 fn check_path_results(
     mut commands: Commands,
     mut state: ResMut<UpdatePathsState>,
@@ -202,7 +440,9 @@ fn check_path_results(
         let mut entity_commands = commands.entity(entity);
         match path {
             Some(path) => {
-                entity_commands.insert(ScheduledPath::new(path));
+                entity_commands
+                    .insert(ScheduledPath::new(path))
+                    .remove::<FormationSlot>();
             }
             None => {
                 entity_commands.remove::<ScheduledPath>();
@@ -233,3 +473,81 @@ fn remove_path_targets(
         }
     }
 }
+
+/// Returns the slot with its offset shrunk towards the center of the
+/// formation so that the slot is accessible at the end of the shared path.
+/// A slot at the very center, i.e. at the end of the shared path, is returned
+/// if no shrunk offset is accessible.
+///
+/// # Arguments
+///
+/// * `end` - the last point of the shared path.
+///
+/// * `heading` - direction of the last segment of the shared path.
+fn accessible_slot(
+    finder: &PathFinder,
+    end: Vec2,
+    heading: Vec2,
+    slot: FormationSlot,
+) -> FormationSlot {
+    for scale in SLOT_SCALES {
+        let candidate = FormationSlot::new(slot.group(), scale * slot.offset());
+        let offset = candidate.oriented(heading);
+        // The point half way to the slot is checked as well so that slots
+        // behind thin obstacles are avoided.
+        if finder.is_accessible(end + offset) && finder.is_accessible(end + 0.5 * offset) {
+            return candidate;
+        }
+    }
+
+    FormationSlot::new(slot.group(), Vec2::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use parry2d::{math::Point, shape::Triangle};
+
+    use super::*;
+
+    #[test]
+    fn test_accessible_slot() {
+        // Accessible area is a 20m x 10m rectangle.
+        let finder = PathFinder::from_triangles(
+            vec![
+                Triangle::new(
+                    Point::new(0., 0.),
+                    Point::new(20., 0.),
+                    Point::new(20., 10.),
+                ),
+                Triangle::new(
+                    Point::new(0., 0.),
+                    Point::new(20., 10.),
+                    Point::new(0., 10.),
+                ),
+            ],
+            vec![],
+        );
+        let group = Entity::from_raw(1);
+        let end = Vec2::new(10., 8.);
+
+        let slot = accessible_slot(&finder, end, Vec2::Y, FormationSlot::new(group, Vec2::X));
+        assert_eq!(slot.offset(), Vec2::X);
+        assert_eq!(slot.group(), group);
+
+        let slot = accessible_slot(
+            &finder,
+            end,
+            Vec2::Y,
+            FormationSlot::new(group, Vec2::new(-4., 6.)),
+        );
+        assert_eq!(slot.offset(), Vec2::new(-1., 1.5));
+
+        let slot = accessible_slot(
+            &finder,
+            end,
+            Vec2::Y,
+            FormationSlot::new(group, Vec2::new(0., 40.)),
+        );
+        assert_eq!(slot.offset(), Vec2::ZERO);
+    }
+}
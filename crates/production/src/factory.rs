@@ -0,0 +1,236 @@
+use std::f32::consts::PI;
+
+use ahash::AHashMap;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use de_core::{
+    objects::{ActiveObjectType, MovableSolid, ObjectType, StaticSolid, PLAYER_MAX_UNITS},
+    player::Player,
+    projection::{ToFlat, ToMsl},
+    stages::GameStage,
+    state::GameState,
+};
+use de_economy::PowerSupply;
+use de_index::{QueryCollider, SpatialQuery};
+use de_map::size::MapBounds;
+use de_objects::{ColliderCache, IchnographyCache, ObjectCache, EXCLUSION_OFFSET};
+use de_spawner::{ObjectCounter, SpawnBundle};
+use glam::{Mat2, Vec2};
+use iyes_loopless::prelude::*;
+use parry3d::math::Isometry;
+
+use crate::{
+    queue::ProductionQueue,
+    rally::{RallyPoint, RallyTargets, Rallying},
+};
+
+/// Produced units are spawned this far (in meters) from the edge of the
+/// exclusion area of the producing building.
+const SPAWN_DISTANCE: f32 = 2.;
+/// Angle (in radians) between neighbouring candidate spawn positions at the
+/// same distance from the producing building.
+const SPAWN_ANGLE_STEP: f32 = PI / 12.;
+/// Number of candidate spawn positions at the same distance from the
+/// producing building.
+const SPAWN_ANGLES: usize = 23;
+/// Candidate spawn positions are searched at this many distances from the
+/// producing building.
+const SPAWN_RINGS: usize = 4;
+/// Difference (in meters) between neighbouring candidate spawn distances.
+const SPAWN_RING_SPACING: f32 = 4.;
+
+pub(crate) struct FactoryPlugin;
+
+impl Plugin for FactoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(GameStage::Update, produce.run_in_state(GameState::Playing));
+    }
+}
+
+/// Advances production in all buildings and spawns finished units next to
+/// them, preferably on the side facing their rally point, see
+/// [`SpawnArea::position`]. Production in buildings which consume energy is
+/// slowed down proportionally to their power supply. Finished units wait in
+/// the building while their player has the maximum number of units.
+fn produce(
+    mut commands: Commands,
+    time: Res<Time>,
+    cache: Res<ObjectCache>,
+    counter: Res<ObjectCounter>,
+    mut factories: Query<(
+        &Player,
+        &ObjectType,
+        &Transform,
+        &mut ProductionQueue,
+        Option<&PowerSupply>,
+        Option<&RallyPoint>,
+    )>,
+    targets: RallyTargets,
+    area: SpawnArea,
+) {
+    // Units spawned during this frame are not yet included in the counter.
+    let mut unit_counts: AHashMap<Player, usize> = AHashMap::new();
+    // Units spawned during this frame are not yet indexed.
+    let mut spawned: Vec<Vec2> = Vec::new();
+
+    for (&player, &object_type, transform, mut queue, supply, rally_point) in factories.iter_mut() {
+        if queue.is_empty() {
+            continue;
+        }
+
+        let delta = match supply {
+            Some(supply) => time.delta().mul_f32(supply.efficiency()),
+            None => time.delta(),
+        };
+        let (unit_type, progress) = queue.advance(delta).unwrap();
+        let unit_object_type = ObjectType::Active(ActiveObjectType::Unit(unit_type));
+        if progress < cache.get(unit_object_type).build_time() {
+            continue;
+        }
+
+        let unit_count = unit_counts
+            .entry(player)
+            .or_insert_with(|| counter.unit_count(player));
+        if *unit_count >= PLAYER_MAX_UNITS {
+            continue;
+        }
+        *unit_count += 1;
+        queue.pop();
+
+        let center = transform.translation.to_flat();
+        let rally_target = rally_point.and_then(|rally_point| rally_point.path_target(&targets));
+        let direction = match rally_target {
+            Some(target) => target.location() - center,
+            None => (transform.rotation * Vec3::X).to_flat(),
+        };
+        let distance =
+            cache.get_ichnography(object_type).radius() + EXCLUSION_OFFSET + SPAWN_DISTANCE;
+        let position = area.position(unit_object_type, center, direction, distance, &spawned);
+        spawned.push(position);
+
+        let mut entity_commands = commands.spawn_bundle(SpawnBundle::new(
+            unit_object_type,
+            Transform::from_translation(position.to_msl()),
+        ));
+        entity_commands.insert(player);
+        if let Some(target) = rally_target {
+            entity_commands.insert(Rallying::new(target));
+        }
+    }
+}
+
+#[derive(SystemParam)]
+struct SpawnArea<'w, 's> {
+    cache: Res<'w, ObjectCache>,
+    bounds: Res<'w, MapBounds>,
+    solids: SpatialQuery<'w, 's, Entity, Or<(With<StaticSolid>, With<MovableSolid>)>>,
+}
+
+impl<'w, 's> SpawnArea<'w, 's> {
+    /// Returns the position where a produced unit is spawned: the first
+    /// candidate (see [`spawn_candidates`]) where the unit fits inside the
+    /// map without colliding with other objects. The first candidate,
+    /// clamped to the map, is returned if there is no such position.
+    ///
+    /// # Arguments
+    ///
+    /// * `unit_type` - type of the spawned unit.
+    ///
+    /// * `spawned` - positions of units spawned during the current frame.
+    ///   These are not yet indexed and therefore avoided separately.
+    ///
+    /// See [`spawn_candidates`] for the other arguments.
+    fn position(
+        &self,
+        unit_type: ObjectType,
+        center: Vec2,
+        direction: Vec2,
+        distance: f32,
+        spawned: &[Vec2],
+    ) -> Vec2 {
+        let radius = self.cache.get_ichnography(unit_type).radius();
+        let min = self.bounds.min() + Vec2::splat(radius);
+        let max = self.bounds.max() - Vec2::splat(radius);
+
+        spawn_candidates(center, direction, distance)
+            .find(|&position| {
+                if position.cmplt(min).any() || position.cmpgt(max).any() {
+                    return false;
+                }
+                if spawned
+                    .iter()
+                    .any(|other| other.distance(position) < 2. * radius)
+                {
+                    return false;
+                }
+
+                let collider = QueryCollider::new(
+                    self.cache.get_collider(unit_type),
+                    Isometry::new(position.to_msl().into(), Vec3::ZERO.into()),
+                );
+                !self.solids.collides(&collider)
+            })
+            .unwrap_or_else(|| {
+                spawn_candidates(center, direction, distance)
+                    .next()
+                    .unwrap()
+                    .clamp(min, max)
+            })
+    }
+}
+
+/// Returns candidate positions of a produced unit ordered by preference.
+/// Candidates at the same distance alternate on either side of the preferred
+/// direction, getting further from it, before further distances are tried.
+///
+/// # Arguments
+///
+/// * `center` - position of the producing building.
+///
+/// * `direction` - preferred direction (from the building) in which the unit
+///   is spawned. X axis is used if it is zero.
+///
+/// * `distance` - minimum distance of the unit from the building center.
+fn spawn_candidates(center: Vec2, direction: Vec2, distance: f32) -> impl Iterator<Item = Vec2> {
+    let direction = if direction == Vec2::ZERO {
+        Vec2::X
+    } else {
+        direction.normalize()
+    };
+
+    (0..SPAWN_RINGS).flat_map(move |ring| {
+        let distance = distance + ring as f32 * SPAWN_RING_SPACING;
+        (0..SPAWN_ANGLES).map(move |i| {
+            // Steps 0, 1, -1, 2, -2, ...
+            let step = ((i + 1) / 2) as f32;
+            let angle = if i % 2 == 0 { -step } else { step } * SPAWN_ANGLE_STEP;
+            center + distance * (Mat2::from_angle(angle) * direction)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawn_candidates() {
+        let candidates: Vec<Vec2> =
+            spawn_candidates(Vec2::new(1., 2.), Vec2::new(0., -3.), 10.).collect();
+        assert_eq!(candidates.len(), SPAWN_RINGS * SPAWN_ANGLES);
+        assert_eq!(candidates[0], Vec2::new(1., -8.));
+        // Rotated counter clockwise, i.e. towards positive X.
+        assert!(candidates[1].x > 1.);
+        assert!(candidates[2].x < 1.);
+        for candidate in &candidates[..SPAWN_ANGLES] {
+            assert!((candidate.distance(Vec2::new(1., 2.)) - 10.).abs() < 1e-4);
+        }
+        assert!(
+            (candidates[SPAWN_ANGLES].distance(Vec2::new(1., -8. - SPAWN_RING_SPACING))) < 1e-4
+        );
+
+        let first = spawn_candidates(Vec2::new(1., 2.), Vec2::ZERO, 10.)
+            .next()
+            .unwrap();
+        assert_eq!(first, Vec2::new(11., 2.));
+    }
+}
@@ -0,0 +1,24 @@
+//! This crate implements production of units in buildings. Each building
+//! with a [`de_objects::Factory`] component has a [`ProductionQueue`] which
+//! is manipulated with [`ProductionEvent`]. Produced units and units spawned
+//! next to the building are sent to the [`RallyPoint`] of the building, see
+//! [`SetRallyPointEvent`].
+
+use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
+use factory::FactoryPlugin;
+use queue::QueuePlugin;
+pub use queue::{ProductionCommand, ProductionEvent, ProductionQueue, MAX_QUEUE_LENGTH};
+use rally::RallyPlugin;
+pub use rally::{RallyPoint, SetRallyPointEvent};
+
+mod factory;
+mod queue;
+mod rally;
+
+pub struct ProductionPluginGroup;
+
+impl PluginGroup for ProductionPluginGroup {
+    fn build(&mut self, group: &mut PluginGroupBuilder) {
+        group.add(QueuePlugin).add(FactoryPlugin).add(RallyPlugin);
+    }
+}
@@ -0,0 +1,226 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+use de_core::{
+    objects::{ActiveObjectType, ObjectType, UnitType},
+    player::Player,
+    stages::GameStage,
+    state::GameState,
+};
+use de_economy::Stockpiles;
+use de_objects::{Factory, ObjectCache};
+use iyes_loopless::prelude::*;
+
+/// Maximum number of units queued in a single building (including the unit
+/// in production).
+pub const MAX_QUEUE_LENGTH: usize = 8;
+
+pub(crate) struct QueuePlugin;
+
+impl Plugin for QueuePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ProductionEvent>().add_system_set_to_stage(
+            GameStage::PreUpdate,
+            SystemSet::new()
+                .with_system(init.run_in_state(GameState::Playing))
+                .with_system(command.run_in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Send this event to manipulate the production queue of a building.
+pub struct ProductionEvent {
+    building: Entity,
+    command: ProductionCommand,
+}
+
+impl ProductionEvent {
+    pub fn new(building: Entity, command: ProductionCommand) -> Self {
+        Self { building, command }
+    }
+
+    fn building(&self) -> Entity {
+        self.building
+    }
+
+    fn command(&self) -> ProductionCommand {
+        self.command
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProductionCommand {
+    /// Appends a unit to the end of the queue. The cost of the unit is spent
+    /// immediately. The command is ignored if the player cannot afford the
+    /// unit, if the queue is full or if the building cannot produce units of
+    /// the type.
+    Enqueue(UnitType),
+    /// Removes the unit at a given position (counted from the unit in
+    /// production) from the queue. The cost of the unit is refunded.
+    Cancel(usize),
+    /// Moves the unit at position `from` to position `to`. Production
+    /// progress of the moved unit is retained.
+    Reorder { from: usize, to: usize },
+}
+
+/// Units waiting to be produced in a building. Only the first unit in the
+/// queue is being produced at a time.
+#[derive(Component, Default)]
+pub struct ProductionQueue {
+    items: VecDeque<Item>,
+}
+
+struct Item {
+    unit_type: UnitType,
+    progress: Duration,
+}
+
+impl ProductionQueue {
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.items.len() >= MAX_QUEUE_LENGTH
+    }
+
+    /// Iterates over queued unit types starting with the unit in production.
+    pub fn iter(&self) -> impl Iterator<Item = UnitType> + '_ {
+        self.items.iter().map(|item| item.unit_type)
+    }
+
+    /// Returns the time spent so far on production of the unit at a given
+    /// position, or None if the position is out of bounds.
+    pub fn progress(&self, index: usize) -> Option<Duration> {
+        self.items.get(index).map(|item| item.progress)
+    }
+
+    fn push(&mut self, unit_type: UnitType) {
+        debug_assert!(!self.is_full());
+        self.items.push_back(Item {
+            unit_type,
+            progress: Duration::ZERO,
+        });
+    }
+
+    fn remove(&mut self, index: usize) -> Option<UnitType> {
+        self.items.remove(index).map(|item| item.unit_type)
+    }
+
+    /// Returns false (and does nothing) if any of the positions is out of
+    /// bounds.
+    fn reorder(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.items.len() || to >= self.items.len() {
+            return false;
+        }
+        let item = self.items.remove(from).unwrap();
+        self.items.insert(to, item);
+        true
+    }
+
+    /// Advances production of the first unit in the queue and returns its
+    /// type and total production progress. None is returned if the queue is
+    /// empty.
+    pub(crate) fn advance(&mut self, delta: Duration) -> Option<(UnitType, Duration)> {
+        self.items.front_mut().map(|item| {
+            item.progress += delta;
+            (item.unit_type, item.progress)
+        })
+    }
+
+    /// Removes the first unit from the queue.
+    pub(crate) fn pop(&mut self) -> Option<UnitType> {
+        self.items.pop_front().map(|item| item.unit_type)
+    }
+}
+
+fn init(mut commands: Commands, factories: Query<Entity, Added<Factory>>) {
+    for entity in factories.iter() {
+        commands.entity(entity).insert(ProductionQueue::default());
+    }
+}
+
+fn command(
+    mut events: EventReader<ProductionEvent>,
+    mut stockpiles: ResMut<Stockpiles>,
+    cache: Res<ObjectCache>,
+    mut factories: Query<(&Player, &Factory, &mut ProductionQueue)>,
+) {
+    for event in events.iter() {
+        let (&player, factory, mut queue) = match factories.get_mut(event.building()) {
+            Ok(factory) => factory,
+            Err(_) => continue,
+        };
+        let cost = |unit_type| {
+            cache
+                .get(ObjectType::Active(ActiveObjectType::Unit(unit_type)))
+                .cost()
+        };
+
+        match event.command() {
+            ProductionCommand::Enqueue(unit_type) => {
+                if factory.produces(unit_type)
+                    && !queue.is_full()
+                    && stockpiles.spend(player, cost(unit_type))
+                {
+                    queue.push(unit_type);
+                }
+            }
+            ProductionCommand::Cancel(index) => {
+                if let Some(unit_type) = queue.remove(index) {
+                    stockpiles.add(player, cost(unit_type));
+                }
+            }
+            ProductionCommand::Reorder { from, to } => {
+                queue.reorder(from, to);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue() {
+        let mut queue = ProductionQueue::default();
+        assert!(queue.is_empty());
+        assert!(queue.advance(Duration::from_secs(1)).is_none());
+
+        for _ in 0..MAX_QUEUE_LENGTH {
+            queue.push(UnitType::Attacker);
+        }
+        assert!(queue.is_full());
+        assert_eq!(queue.len(), MAX_QUEUE_LENGTH);
+
+        assert_eq!(
+            queue.advance(Duration::from_secs(2)),
+            Some((UnitType::Attacker, Duration::from_secs(2)))
+        );
+        assert_eq!(
+            queue.advance(Duration::from_secs(3)),
+            Some((UnitType::Attacker, Duration::from_secs(5)))
+        );
+        assert_eq!(queue.progress(0), Some(Duration::from_secs(5)));
+        assert_eq!(queue.progress(1), Some(Duration::ZERO));
+        assert_eq!(queue.progress(MAX_QUEUE_LENGTH), None);
+
+        assert!(queue.reorder(0, 2));
+        assert_eq!(queue.progress(0), Some(Duration::ZERO));
+        assert_eq!(queue.progress(2), Some(Duration::from_secs(5)));
+        assert!(!queue.reorder(0, MAX_QUEUE_LENGTH));
+
+        assert_eq!(queue.remove(2), Some(UnitType::Attacker));
+        assert!(queue.progress(2).unwrap().is_zero());
+        assert_eq!(queue.remove(MAX_QUEUE_LENGTH), None);
+        assert!(!queue.is_full());
+
+        assert_eq!(queue.pop(), Some(UnitType::Attacker));
+        assert_eq!(queue.len(), MAX_QUEUE_LENGTH - 2);
+    }
+}
@@ -0,0 +1,313 @@
+#![allow(clippy::forget_non_drop)] // Needed because of #[derive(Bundle)]
+
+use ahash::AHashSet;
+use bevy::prelude::*;
+use de_core::{
+    objects::{MovableSolid, Playable},
+    player::Player,
+    projection::{ToFlat, ToMsl},
+    stages::GameStage,
+    state::GameState,
+};
+use de_objects::Factory;
+use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPath};
+use de_terrain::CircleMarker;
+use glam::Vec2;
+use iyes_loopless::prelude::*;
+
+/// Radius (in meters) of the circle marking a rally point on the terrain.
+const MARKER_RADIUS: f32 = 2.;
+/// Units spawned at most this far (in meters) from a building of their player
+/// are sent to its rally point.
+const NEARBY_DISTANCE: f32 = 20.;
+
+pub(crate) struct RallyPlugin;
+
+impl Plugin for RallyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SetRallyPointEvent>()
+            .add_system_set_to_stage(
+                GameStage::PreUpdate,
+                SystemSet::new()
+                    .with_system(set_rally_points.run_in_state(GameState::Playing))
+                    .with_system(rally.run_in_state(GameState::Playing))
+                    .with_system(rally_nearby.run_in_state(GameState::Playing)),
+            )
+            .add_system_to_stage(
+                GameStage::PostUpdate,
+                update_markers.run_in_state(GameState::Playing),
+            );
+    }
+}
+
+/// Send this event to set a rally point of a building. The event is ignored
+/// if the building is not able to produce units.
+pub struct SetRallyPointEvent {
+    building: Entity,
+    rally_point: RallyPoint,
+}
+
+impl SetRallyPointEvent {
+    pub fn new(building: Entity, rally_point: RallyPoint) -> Self {
+        Self {
+            building,
+            rally_point,
+        }
+    }
+
+    fn building(&self) -> Entity {
+        self.building
+    }
+
+    fn rally_point(&self) -> RallyPoint {
+        self.rally_point
+    }
+}
+
+/// Units produced by a building with this component are sent to the rally
+/// point. So are other units spawned next to the building (e.g. units
+/// placed on the map). If there are multiple such buildings of the unit's
+/// player, the nearest one is used.
+#[derive(Component, Copy, Clone, Debug, PartialEq)]
+pub enum RallyPoint {
+    Location(Vec2),
+    /// Units are sent as close as possible to the target entity (at the time
+    /// of their production).
+    Target(Entity),
+}
+
+impl RallyPoint {
+    /// Returns the current location of the rally point or None if its target
+    /// entity no longer exists.
+    pub(crate) fn location(&self, targets: &RallyTargets) -> Option<Vec2> {
+        match *self {
+            Self::Location(location) => Some(location),
+            Self::Target(target) => targets
+                .get(target)
+                .ok()
+                .map(|transform| transform.translation.to_flat()),
+        }
+    }
+
+    /// Returns a path target leading to the rally point or None if its target
+    /// entity no longer exists.
+    pub(crate) fn path_target(&self, targets: &RallyTargets) -> Option<PathTarget> {
+        let properties = match self {
+            Self::Location(_) => PathQueryProps::exact(),
+            Self::Target(_) => PathQueryProps::new(0., f32::INFINITY),
+        };
+        self.location(targets)
+            .map(|location| PathTarget::new(location, properties, false))
+    }
+}
+
+pub(crate) type RallyTargets<'w, 's> = Query<'w, 's, &'static Transform, Without<RallyMarker>>;
+
+/// Freshly produced units with this component are sent to the path target
+/// once they are fully spawned.
+#[derive(Component)]
+pub(crate) struct Rallying(PathTarget);
+
+impl Rallying {
+    pub(crate) fn new(target: PathTarget) -> Self {
+        Self(target)
+    }
+}
+
+/// Marker of a rally point of a building of the local player.
+#[derive(Component)]
+pub(crate) struct RallyMarker {
+    building: Entity,
+}
+
+#[derive(Bundle)]
+struct RallyMarkerBundle {
+    transform: Transform,
+    global_transform: GlobalTransform,
+    visibility: Visibility,
+    computed_visibility: ComputedVisibility,
+    circle: CircleMarker,
+    marker: RallyMarker,
+}
+
+impl RallyMarkerBundle {
+    fn new(building: Entity, location: Vec2) -> Self {
+        let transform = Transform::from_translation(location.to_msl());
+        Self {
+            transform,
+            global_transform: transform.into(),
+            visibility: Visibility::visible(),
+            computed_visibility: ComputedVisibility::not_visible(),
+            circle: CircleMarker::new(MARKER_RADIUS),
+            marker: RallyMarker { building },
+        }
+    }
+}
+
+fn set_rally_points(
+    mut commands: Commands,
+    mut events: EventReader<SetRallyPointEvent>,
+    factories: Query<(), With<Factory>>,
+) {
+    for event in events.iter() {
+        if factories.contains(event.building()) {
+            commands
+                .entity(event.building())
+                .insert(event.rally_point());
+        }
+    }
+}
+
+fn rally(
+    mut commands: Commands,
+    mut path_events: EventWriter<UpdateEntityPath>,
+    units: Query<(Entity, &Rallying), With<MovableSolid>>,
+) {
+    for (entity, rallying) in units.iter() {
+        commands.entity(entity).remove::<Rallying>();
+        path_events.send(UpdateEntityPath::new(entity, rallying.0));
+    }
+}
+
+/// Sends freshly spawned units, which were not produced by a building with a
+/// rally point, to the rally point of the nearest building of their player
+/// within [`NEARBY_DISTANCE`].
+fn rally_nearby(
+    mut path_events: EventWriter<UpdateEntityPath>,
+    units: Query<(Entity, &Player, &Transform), (Added<MovableSolid>, Without<Rallying>)>,
+    buildings: Query<(&Player, &Transform, &RallyPoint)>,
+    targets: RallyTargets,
+) {
+    for (entity, &player, transform) in units.iter() {
+        let position = transform.translation.to_flat();
+        let nearest = buildings
+            .iter()
+            .filter(|(&owner, _, _)| owner == player)
+            .map(|(_, building_transform, &rally_point)| {
+                let distance = building_transform.translation.to_flat().distance(position);
+                (distance, rally_point)
+            })
+            .filter(|&(distance, _)| distance <= NEARBY_DISTANCE)
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+
+        if let Some(target) = nearest.and_then(|(_, rally_point)| rally_point.path_target(&targets))
+        {
+            path_events.send(UpdateEntityPath::new(entity, target));
+        }
+    }
+}
+
+/// Keeps a marker at rally points of all buildings of the local player.
+fn update_markers(
+    mut commands: Commands,
+    buildings: Query<(Entity, &RallyPoint), With<Playable>>,
+    targets: RallyTargets,
+    mut markers: Query<(Entity, &RallyMarker, &mut Transform)>,
+) {
+    let mut marked = AHashSet::new();
+
+    for (entity, marker, mut transform) in markers.iter_mut() {
+        let location = buildings
+            .get(marker.building)
+            .ok()
+            .and_then(|(_, rally_point)| rally_point.location(&targets));
+
+        match location {
+            Some(location) => {
+                marked.insert(marker.building);
+                let translation = location.to_msl();
+                // Access the component mutably only when really needed for
+                // optimal Bevy change detection.
+                if transform.translation != translation {
+                    transform.translation = translation;
+                }
+            }
+            None => {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+
+    for (building, rally_point) in buildings.iter() {
+        if marked.contains(&building) {
+            continue;
+        }
+        if let Some(location) = rally_point.location(&targets) {
+            commands.spawn_bundle(RallyMarkerBundle::new(building, location));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rally points evaluated by the test system.
+    struct Points(Vec<RallyPoint>);
+
+    /// Locations and path targets of the rally points as returned by
+    /// [`RallyPoint::location`] and [`RallyPoint::path_target`].
+    struct Results(Vec<(Option<Vec2>, Option<PathTarget>)>);
+
+    fn help_system(mut commands: Commands, points: Res<Points>, targets: RallyTargets) {
+        let results = points
+            .0
+            .iter()
+            .map(|point| (point.location(&targets), point.path_target(&targets)))
+            .collect();
+        commands.insert_resource(Results(results));
+    }
+
+    #[test]
+    fn test_rally_point() {
+        let mut world = World::default();
+
+        let target = world.spawn().insert(Transform::from_xyz(3., 1., -4.)).id();
+        let despawned = world.spawn().insert(Transform::from_xyz(5., 0., 5.)).id();
+        world.despawn(despawned);
+        let marker = world
+            .spawn()
+            .insert(Transform::from_xyz(7., 0., 7.))
+            .insert(RallyMarker { building: target })
+            .id();
+
+        world.insert_resource(Points(vec![
+            RallyPoint::Location(Vec2::new(-1., 2.)),
+            RallyPoint::Target(target),
+            RallyPoint::Target(despawned),
+            RallyPoint::Target(marker),
+        ]));
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(help_system);
+        stage.run(&mut world);
+
+        let results = &world.get_resource::<Results>().unwrap().0;
+
+        let (location, path_target) = results[0];
+        assert_eq!(location, Some(Vec2::new(-1., 2.)));
+        let path_target = path_target.unwrap();
+        assert_eq!(path_target.location(), Vec2::new(-1., 2.));
+        assert_eq!(path_target.properties().distance(), 0.);
+        assert_eq!(path_target.properties().max_distance(), 0.);
+        assert!(!path_target.permanent());
+
+        let (location, path_target) = results[1];
+        assert_eq!(location, Some(Vec2::new(3., 4.)));
+        let path_target = path_target.unwrap();
+        assert_eq!(path_target.location(), Vec2::new(3., 4.));
+        assert_eq!(path_target.properties().distance(), 0.);
+        assert_eq!(path_target.properties().max_distance(), f32::INFINITY);
+        assert!(!path_target.permanent());
+
+        // Target entity no longer exists.
+        let (location, path_target) = results[2];
+        assert!(location.is_none());
+        assert!(path_target.is_none());
+
+        // Rally point markers are never targets.
+        let (location, path_target) = results[3];
+        assert!(location.is_none());
+        assert!(path_target.is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use ahash::AHashMap;
 use bevy::{
     pbr::{NotShadowCaster, NotShadowReceiver},
     prelude::*,
@@ -18,6 +19,11 @@ use iyes_loopless::prelude::*;
 /// Vertical distance in meters between the bar center and the top of the
 /// parent entity collider.
 const BAR_HEIGHT: f32 = 2.;
+/// Vertical offset of the progress bar relative to the (health) bar, in
+/// units of the bar mesh.
+const PROGRESS_BAR_OFFSET: f32 = -0.4;
+const HEALTH_COLOR: Color = Color::rgba(0.6, 1., 0.6, 0.75);
+const PROGRESS_COLOR: Color = Color::rgba(0.5, 0.7, 1., 0.75);
 
 pub(crate) struct BarsPlugin;
 
@@ -26,13 +32,15 @@ impl Plugin for BarsPlugin {
         app.add_plugin(MaterialPlugin::<BarMaterial>::default())
             .add_event::<UpdateBarValueEvent>()
             .add_event::<UpdateBarVisibilityEvent>()
+            .add_event::<UpdateProgressBarEvent>()
             .add_enter_system(GameState::Loading, setup)
             .add_system_set_to_stage(
                 GameStage::PostUpdate,
                 SystemSet::new()
                     .with_system(spawn)
                     .with_system(update_value)
-                    .with_system(update_visibility),
+                    .with_system(update_visibility)
+                    .with_system(update_progress.run_in_state(GameState::Playing)),
             );
     }
 }
@@ -103,11 +111,58 @@ impl UpdateBarVisibilityEvent {
     }
 }
 
-struct BarMesh(Handle<Mesh>);
+/// An event which shows, updates or hides a progress bar of an entity (e.g.
+/// construction progress). The progress bar is displayed right below the
+/// (health) bar of the entity and, unlike the (health) bar, it is visible
+/// whenever it is shown by this event.
+pub struct UpdateProgressBarEvent {
+    entity: Entity,
+    value: Option<f32>,
+}
+
+impl UpdateProgressBarEvent {
+    /// Crates a new event which shows the progress bar with a given value.
+    ///
+    /// # Panics
+    ///
+    /// May panic if the value is not between 0. and 1. (inclusive).
+    pub fn new(entity: Entity, value: f32) -> Self {
+        debug_assert!((0. ..=1.).contains(&value));
+        Self {
+            entity,
+            value: Some(value),
+        }
+    }
+
+    /// Crates a new event which hides the progress bar.
+    pub fn hide(entity: Entity) -> Self {
+        Self {
+            entity,
+            value: None,
+        }
+    }
+
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    fn value(&self) -> Option<f32> {
+        self.value
+    }
+}
+
+struct BarMesh {
+    bar: Handle<Mesh>,
+    progress: Handle<Mesh>,
+}
 
 impl BarMesh {
     fn mesh(&self) -> Handle<Mesh> {
-        self.0.clone()
+        self.bar.clone()
+    }
+
+    fn progress_mesh(&self) -> Handle<Mesh> {
+        self.progress.clone()
     }
 }
 
@@ -116,11 +171,19 @@ impl BarMesh {
 struct BarMaterial {
     #[uniform(0)]
     value: f32,
+    #[uniform(1)]
+    color: Color,
+}
+
+impl BarMaterial {
+    fn new(value: f32, color: Color) -> Self {
+        Self { value, color }
+    }
 }
 
 impl Default for BarMaterial {
     fn default() -> Self {
-        Self { value: 1. }
+        Self::new(1., HEALTH_COLOR)
     }
 }
 
@@ -141,6 +204,9 @@ impl Material for BarMaterial {
 #[derive(Component)]
 struct BarChild(Entity);
 
+#[derive(Component)]
+struct ProgressBarChild(Entity);
+
 #[derive(Component, Default)]
 struct BarVisibility(u32);
 
@@ -160,7 +226,10 @@ impl BarVisibility {
 }
 
 fn setup(mut commans: Commands, mut meshes: ResMut<Assets<Mesh>>) {
-    commans.insert_resource(BarMesh(meshes.add(bar_mesh(1.5, 0.3))));
+    commans.insert_resource(BarMesh {
+        bar: meshes.add(bar_mesh(1.5, 0.3, 0.)),
+        progress: meshes.add(bar_mesh(1.5, 0.3, PROGRESS_BAR_OFFSET)),
+    });
 }
 
 fn spawn(
@@ -171,15 +240,7 @@ fn spawn(
     entities: Query<(Entity, &ObjectType), Added<Active>>,
 ) {
     for (entity, &object_type) in entities.iter() {
-        let height = cache
-            .as_ref()
-            .unwrap()
-            .get_collider(object_type)
-            .aabb()
-            .maxs
-            .y
-            + BAR_HEIGHT;
-        let transform = Transform::from_translation(height * Vec3::Y);
+        let transform = bar_transform(cache.as_ref().unwrap(), object_type);
 
         let material = materials.add(BarMaterial::default());
 
@@ -232,15 +293,106 @@ fn update_visibility(
     }
 }
 
-fn bar_mesh(width: f32, height: f32) -> Mesh {
+fn update_progress(
+    mut commands: Commands,
+    cache: Res<ObjectCache>,
+    mesh: Res<BarMesh>,
+    mut materials: ResMut<Assets<BarMaterial>>,
+    parents: Query<(&ObjectType, Option<&ProgressBarChild>)>,
+    bars: Query<&Handle<BarMaterial>>,
+    mut events: EventReader<UpdateProgressBarEvent>,
+) {
+    // Only the last event for each entity is applied so that at most a
+    // single progress bar is spawned.
+    let mut values = AHashMap::new();
+    for event in events.iter() {
+        values.insert(event.entity(), event.value());
+    }
+
+    for (entity, value) in values {
+        let (&object_type, child) = match parents.get(entity) {
+            Ok(parent) => parent,
+            Err(_) => continue,
+        };
+
+        // Value of a newly spawned progress bar, if any.
+        let spawn_value = match (child, value) {
+            (Some(child), Some(value)) => {
+                match bars
+                    .get(child.0)
+                    .ok()
+                    .and_then(|handle| materials.get_mut(handle))
+                {
+                    Some(material) => {
+                        material.value = value;
+                        None
+                    }
+                    None => {
+                        warn!(
+                            "Progress bar of {:?} is missing, spawning a new one.",
+                            entity
+                        );
+                        if bars.contains(child.0) {
+                            commands.entity(child.0).despawn();
+                        }
+                        Some(value)
+                    }
+                }
+            }
+            (Some(child), None) => {
+                if bars.contains(child.0) {
+                    commands.entity(child.0).despawn();
+                }
+                commands.entity(entity).remove::<ProgressBarChild>();
+                None
+            }
+            (None, value) => value,
+        };
+
+        if let Some(value) = spawn_value {
+            let bar_entity = commands
+                .spawn_bundle(MaterialMeshBundle::<BarMaterial> {
+                    mesh: mesh.progress_mesh(),
+                    material: materials.add(BarMaterial::new(value, PROGRESS_COLOR)),
+                    transform: bar_transform(cache.as_ref(), object_type),
+                    ..Default::default()
+                })
+                .insert(NotShadowCaster)
+                .insert(NotShadowReceiver)
+                .id();
+
+            commands
+                .entity(entity)
+                .add_child(bar_entity)
+                .insert(ProgressBarChild(bar_entity));
+        }
+    }
+}
+
+/// Returns local transform of bars of an object.
+fn bar_transform(cache: &ObjectCache, object_type: ObjectType) -> Transform {
+    let height = cache.get_collider(object_type).aabb().maxs.y + BAR_HEIGHT;
+    Transform::from_translation(height * Vec3::Y)
+}
+
+/// Creates a bar mesh.
+///
+/// # Arguments
+///
+/// * `width` - width of the bar.
+///
+/// * `height` - height of the bar.
+///
+/// * `offset` - vertical offset of the bar center.
+fn bar_mesh(width: f32, height: f32, offset: f32) -> Mesh {
     let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
     mesh.insert_attribute(
         Mesh::ATTRIBUTE_POSITION,
         vec![
-            [-0.5 * width, 0.5 * height, 0.],
-            [-0.5 * width, -0.5 * height, 0.],
-            [0.5 * width, -0.5 * height, 0.],
-            [0.5 * width, 0.5 * height, 0.],
+            [-0.5 * width, offset + 0.5 * height, 0.],
+            [-0.5 * width, offset - 0.5 * height, 0.],
+            [0.5 * width, offset - 0.5 * height, 0.],
+            [0.5 * width, offset + 0.5 * height, 0.],
         ],
     );
     mesh.insert_attribute(
@@ -1,5 +1,5 @@
 use bars::BarsPlugin;
-pub use bars::{UpdateBarValueEvent, UpdateBarVisibilityEvent};
+pub use bars::{UpdateBarValueEvent, UpdateBarVisibilityEvent, UpdateProgressBarEvent};
 use bevy::{app::PluginGroupBuilder, prelude::*};
 
 mod bars;
@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use de_core::{objects::ObjectType, stages::GameStage, state::GameState};
+use de_objects::{Health, ObjectCache};
+use de_signs::{UpdateBarValueEvent, UpdateProgressBarEvent};
+use iyes_loopless::prelude::*;
+
+use crate::spawner::insert_functions;
+
+/// Buildings start their construction with this fraction of their maximum
+/// health. The rest is gained gradually during the construction.
+pub(crate) const INITIAL_HEALTH: f32 = 0.1;
+
+pub(crate) struct ConstructionPlugin;
+
+impl Plugin for ConstructionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            GameStage::Update,
+            construct.run_in_state(GameState::Playing),
+        );
+    }
+}
+
+/// Buildings with this component are under construction. They have no
+/// function (e.g. they neither attack, produce units nor power other
+/// buildings) until the construction is finished.
+#[derive(Component)]
+pub struct UnderConstruction {
+    elapsed: Duration,
+    total: Duration,
+}
+
+impl UnderConstruction {
+    /// # Arguments
+    ///
+    /// * `total` - time it takes to construct the building.
+    pub(crate) fn new(total: Duration) -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            total,
+        }
+    }
+
+    /// Returns construction progress as a number between 0 and 1
+    /// (inclusive).
+    pub fn progress(&self) -> f32 {
+        if self.finished() {
+            1.
+        } else {
+            self.elapsed.as_secs_f32() / self.total.as_secs_f32()
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.elapsed >= self.total
+    }
+
+    /// Advances the construction and returns the gained progress.
+    fn advance(&mut self, delta: Duration) -> f32 {
+        let before = self.progress();
+        self.elapsed += delta;
+        self.progress() - before
+    }
+}
+
+fn construct(
+    mut commands: Commands,
+    time: Res<Time>,
+    cache: Res<ObjectCache>,
+    mut buildings: Query<(Entity, &ObjectType, &mut UnderConstruction, &mut Health)>,
+    mut bars: EventWriter<UpdateBarValueEvent>,
+    mut progress_bars: EventWriter<UpdateProgressBarEvent>,
+) {
+    for (entity, &object_type, mut construction, mut health) in buildings.iter_mut() {
+        let gained = construction.advance(time.delta());
+        let amount = gained * (1. - INITIAL_HEALTH) * health.max();
+        health.heal(amount);
+        bars.send(UpdateBarValueEvent::new(entity, health.fraction()));
+
+        if construction.finished() {
+            info!("Construction of {} finished", object_type);
+            let mut entity_commands = commands.entity(entity);
+            entity_commands.remove::<UnderConstruction>();
+            insert_functions(&mut entity_commands, &cache, object_type);
+            progress_bars.send(UpdateProgressBarEvent::hide(entity));
+        } else {
+            progress_bars.send(UpdateProgressBarEvent::new(entity, construction.progress()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_construction() {
+        let mut construction = UnderConstruction::new(Duration::from_secs(4));
+        assert_eq!(construction.progress(), 0.);
+        assert_eq!(construction.advance(Duration::from_secs(1)), 0.25);
+        assert!(!construction.finished());
+        assert_eq!(construction.advance(Duration::from_secs(2)), 0.5);
+        assert_eq!(construction.advance(Duration::from_secs(2)), 0.25);
+        assert!(construction.finished());
+        assert_eq!(construction.progress(), 1.);
+
+        let construction = UnderConstruction::new(Duration::ZERO);
+        assert!(construction.finished());
+        assert_eq!(construction.progress(), 1.);
+    }
+}
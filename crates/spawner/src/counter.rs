@@ -1,6 +1,6 @@
+use ahash::AHashMap;
 use bevy::prelude::*;
 use de_core::{
-    gconfig::GameConfig,
     objects::{ActiveObjectType, ObjectType, PLAYER_MAX_BUILDINGS, PLAYER_MAX_UNITS},
     player::Player,
     stages::GameStage,
@@ -15,46 +15,51 @@ impl Plugin for CounterPlugin {
     }
 }
 
-/// Current count of buildings and units belonging to local player.
+/// Current count of buildings and units belonging to individual players.
 #[derive(Default)]
 pub struct ObjectCounter {
-    building_count: usize,
-    unit_count: usize,
+    players: AHashMap<Player, PlayerCounter>,
 }
 
 impl ObjectCounter {
-    pub fn building_count(&self) -> usize {
-        self.building_count
+    pub fn building_count(&self, player: Player) -> usize {
+        self.players
+            .get(&player)
+            .map_or(0, |counter| counter.building_count)
     }
 
-    pub fn unit_count(&self) -> usize {
-        self.unit_count
+    pub fn unit_count(&self, player: Player) -> usize {
+        self.players
+            .get(&player)
+            .map_or(0, |counter| counter.unit_count)
     }
 }
 
-fn recount(
-    config: Res<GameConfig>,
-    mut counter: ResMut<ObjectCounter>,
-    objects: Query<(&Player, &ObjectType)>,
-) {
-    counter.building_count = 0;
-    counter.unit_count = 0;
+#[derive(Default)]
+struct PlayerCounter {
+    building_count: usize,
+    unit_count: usize,
+}
+
+fn recount(mut counter: ResMut<ObjectCounter>, objects: Query<(&Player, &ObjectType)>) {
+    counter.players.clear();
 
     for (&player, &object_type) in objects.iter() {
         if let ObjectType::Active(object_type) = object_type {
-            if config.is_local_player(player) {
-                match object_type {
-                    ActiveObjectType::Building(_) => counter.building_count += 1,
-                    ActiveObjectType::Unit(_) => counter.unit_count += 1,
-                }
+            let player_counter = counter.players.entry(player).or_default();
+            match object_type {
+                ActiveObjectType::Building(_) => player_counter.building_count += 1,
+                ActiveObjectType::Unit(_) => player_counter.unit_count += 1,
             }
         }
     }
 
-    if counter.building_count > PLAYER_MAX_BUILDINGS {
-        panic!("Maximum number of buildings surpassed.");
-    }
-    if counter.unit_count > PLAYER_MAX_UNITS {
-        panic!("Maximum number of units surpassed.");
+    for player_counter in counter.players.values() {
+        if player_counter.building_count > PLAYER_MAX_BUILDINGS {
+            panic!("Maximum number of buildings surpassed.");
+        }
+        if player_counter.unit_count > PLAYER_MAX_UNITS {
+            panic!("Maximum number of units surpassed.");
+        }
     }
 }
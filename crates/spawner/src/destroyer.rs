@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use de_core::{stages::GameStage, state::GameState};
+use de_core::{objects::ObjectType, player::Player, stages::GameStage, state::GameState};
 use de_objects::Health;
 use iyes_loopless::prelude::*;
 
@@ -9,7 +9,7 @@ pub(crate) struct DestroyerPlugin;
 
 impl Plugin for DestroyerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_to_stage(
+        app.add_event::<ObjectDestroyedEvent>().add_system_to_stage(
             GameStage::Update,
             destroy
                 .run_in_state(GameState::Playing)
@@ -18,10 +18,75 @@ impl Plugin for DestroyerPlugin {
     }
 }
 
-fn destroy(mut commands: Commands, entities: Query<(Entity, &Health)>) {
-    for (entity, health) in entities.iter() {
+/// This event is sent when an object is destroyed and despawned. The entity
+/// no longer exists when the event is received.
+pub struct ObjectDestroyedEvent {
+    entity: Entity,
+    object_type: ObjectType,
+    player: Option<Player>,
+    position: Vec3,
+    killer: Option<Entity>,
+}
+
+impl ObjectDestroyedEvent {
+    fn new(
+        entity: Entity,
+        object_type: ObjectType,
+        player: Option<Player>,
+        position: Vec3,
+        killer: Option<Entity>,
+    ) -> Self {
+        Self {
+            entity,
+            object_type,
+            player,
+            position,
+            killer,
+        }
+    }
+
+    /// The destroyed (already despawned) entity.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn object_type(&self) -> ObjectType {
+        self.object_type
+    }
+
+    /// Owner of the destroyed object or None if the object was not owned by
+    /// any player.
+    pub fn player(&self) -> Option<Player> {
+        self.player
+    }
+
+    /// Position of the object at the time of its destruction.
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+
+    /// The entity which dealt the last damage to the destroyed object or None
+    /// if no entity damaged the object. The killer might no longer exist.
+    pub fn killer(&self) -> Option<Entity> {
+        self.killer
+    }
+}
+
+fn destroy(
+    mut commands: Commands,
+    entities: Query<(Entity, &ObjectType, Option<&Player>, &Transform, &Health)>,
+    mut events: EventWriter<ObjectDestroyedEvent>,
+) {
+    for (entity, &object_type, player, transform, health) in entities.iter() {
         if health.destroyed() {
             commands.entity(entity).despawn_recursive();
+            events.send(ObjectDestroyedEvent::new(
+                entity,
+                object_type,
+                player.copied(),
+                transform.translation,
+                health.last_attacker(),
+            ));
         }
     }
 }
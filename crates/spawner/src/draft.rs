@@ -4,16 +4,17 @@
 //! An entity marked with a component [`Draft`] is automatically handled and
 //! visualized by the plugin.
 
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 use de_core::{
     objects::{ActiveObjectType, BuildingType, MovableSolid, ObjectType, StaticSolid},
+    player::Player,
     projection::ToFlat,
     stages::GameStage,
     state::GameState,
 };
 use de_index::{ColliderWithCache, IndexLabel, QueryCollider, SpatialQuery};
 use de_map::size::MapBounds;
-use de_objects::{ColliderCache, ObjectCache, EXCLUSION_OFFSET};
+use de_objects::{ColliderCache, ObjectCache, PowerSource, EXCLUSION_OFFSET};
 use iyes_loopless::prelude::*;
 use parry2d::{
     bounding_volume::{Aabb, BoundingVolume},
@@ -41,7 +42,8 @@ impl Plugin for DraftPlugin {
     }
 }
 
-/// Bundle to spawn a construction draft.
+/// Bundle to spawn a construction draft. The player who owns the draft has to
+/// be inserted to the entity as a [`de_core::player::Player`] component.
 #[derive(Bundle)]
 pub struct DraftBundle {
     object_type: ObjectType,
@@ -67,12 +69,33 @@ impl DraftBundle {
 
 #[derive(Component, Default)]
 pub struct Draft {
-    allowed: bool,
+    placeable: bool,
+    affordable: bool,
 }
 
 impl Draft {
+    /// Returns true if the drafted building can be placed at its current
+    /// position and the drafting player can afford it.
     pub fn allowed(&self) -> bool {
-        self.allowed
+        self.placeable && self.affordable
+    }
+
+    /// Returns true if the drafted building can be placed at its current
+    /// position, see [`Placement::placeable`].
+    pub fn placeable(&self) -> bool {
+        self.placeable
+    }
+
+    /// Returns true if the drafting player has enough resources to construct
+    /// the drafted building.
+    pub fn affordable(&self) -> bool {
+        self.affordable
+    }
+
+    /// Sets whether the drafting player has enough resources to construct the
+    /// drafted building. This is kept up to date by the economy.
+    pub fn set_affordable(&mut self, affordable: bool) {
+        self.affordable = affordable;
     }
 }
 
@@ -84,6 +107,54 @@ type NonReadyDrafts<'w, 's> =
 
 type Solids<'w, 's> = SpatialQuery<'w, 's, Entity, Or<(With<StaticSolid>, With<MovableSolid>)>>;
 
+/// System parameter used to check whether buildings can be placed on the
+/// map.
+#[derive(SystemParam)]
+pub struct Placement<'w, 's> {
+    solids: Solids<'w, 's>,
+    sources: Query<'w, 's, (&'static Player, &'static Transform, &'static PowerSource)>,
+    cache: Res<'w, ObjectCache>,
+    bounds: Res<'w, MapBounds>,
+}
+
+impl<'w, 's> Placement<'w, 's> {
+    /// Returns true if a building of a player can be placed at a position,
+    /// i.e. if it is inside the map and does not collide with other objects.
+    /// Buildings which consume energy can be placed only in range of a power
+    /// source of the player.
+    pub fn placeable(
+        &self,
+        object_type: ObjectType,
+        transform: &Transform,
+        player: Player,
+    ) -> bool {
+        let collider = QueryCollider::new(
+            self.cache.get_collider(object_type),
+            Isometry::new(
+                transform.translation.into(),
+                transform.rotation.to_scaled_axis().into(),
+            ),
+        );
+
+        let flat_aabb = collider.world_aabb().to_flat();
+        let shrinked_map = {
+            let aabb = self.bounds.aabb();
+            Aabb::new(aabb.mins + MAP_OFFSET, aabb.maxs - MAP_OFFSET)
+        };
+        let powered = self.cache.get(object_type).power_consumer().is_none() || {
+            let position = transform.translation.to_flat();
+            self.sources
+                .iter()
+                .any(|(&owner, source_transform, source)| {
+                    owner == player
+                        && source_transform.translation.to_flat().distance(position)
+                            <= source.range()
+                })
+        };
+        powered && shrinked_map.contains(&flat_aabb) && !self.solids.collides(&collider)
+    }
+}
+
 fn new_draft(mut commands: Commands, drafts: NonReadyDrafts, cache: Res<ObjectCache>) {
     for (entity, object_type) in drafts.iter() {
         commands
@@ -98,31 +169,18 @@ fn new_draft(mut commands: Commands, drafts: NonReadyDrafts, cache: Res<ObjectCa
     }
 }
 
+/// Updates whether the drafts can be placed at their current positions, see
+/// [`Placement::placeable`].
 fn update_draft(
-    mut drafts: Query<(&Transform, &ObjectType, &mut Draft)>,
-    solids: Solids,
-    cache: Res<ObjectCache>,
-    bounds: Res<MapBounds>,
+    mut drafts: Query<(&Transform, &ObjectType, &Player, &mut Draft)>,
+    placement: Placement,
 ) {
-    for (transform, &object_type, mut draft) in drafts.iter_mut() {
-        let collider = QueryCollider::new(
-            cache.get_collider(object_type),
-            Isometry::new(
-                transform.translation.into(),
-                transform.rotation.to_scaled_axis().into(),
-            ),
-        );
-
-        let flat_aabb = collider.world_aabb().to_flat();
-        let shrinked_map = {
-            let aabb = bounds.aabb();
-            Aabb::new(aabb.mins + MAP_OFFSET, aabb.maxs - MAP_OFFSET)
-        };
-        let allowed = shrinked_map.contains(&flat_aabb) && !solids.collides(&collider);
-        if allowed != draft.allowed {
+    for (transform, &object_type, &player, mut draft) in drafts.iter_mut() {
+        let placeable = placement.placeable(object_type, transform, player);
+        if placeable != draft.placeable {
             // Access the component mutably only when really needed for optimal
             // Bevy change detection.
-            draft.allowed = allowed
+            draft.placeable = placeable
         }
     }
 }
@@ -1,14 +1,18 @@
 //! Object spawning and drafting functionalities.
 
 use bevy::{app::PluginGroupBuilder, prelude::*};
+use construction::ConstructionPlugin;
+pub use construction::UnderConstruction;
 use counter::CounterPlugin;
 pub use counter::ObjectCounter;
 use destroyer::DestroyerPlugin;
+pub use destroyer::ObjectDestroyedEvent;
 use draft::DraftPlugin;
-pub use draft::{Draft, DraftBundle};
+pub use draft::{Draft, DraftBundle, Placement};
 pub use spawner::SpawnBundle;
 use spawner::SpawnerPlugin;
 
+mod construction;
 mod counter;
 mod destroyer;
 mod draft;
@@ -21,6 +25,7 @@ impl PluginGroup for SpawnerPluginGroup {
         group
             .add(CounterPlugin)
             .add(SpawnerPlugin)
+            .add(ConstructionPlugin)
             .add(DraftPlugin)
             .add(DestroyerPlugin);
     }
@@ -28,5 +33,7 @@ impl PluginGroup for SpawnerPluginGroup {
 
 #[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemLabel)]
 pub enum SpawnerLabels {
+    /// Destroyed objects are despawned and [`ObjectDestroyedEvent`] is sent
+    /// in systems with this label.
     Destroyer,
 }
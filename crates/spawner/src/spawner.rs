@@ -1,6 +1,6 @@
 #![allow(clippy::forget_non_drop)] // Needed because of #[derive(Bundle)]
 
-use bevy::prelude::*;
+use bevy::{ecs::system::EntityCommands, prelude::*};
 use de_core::{
     gconfig::GameConfig,
     objects::{Active, ActiveObjectType, MovableSolid, ObjectType, Playable, StaticSolid},
@@ -11,6 +11,8 @@ use de_core::{
 use de_objects::{InitialHealths, ObjectCache};
 use iyes_loopless::prelude::*;
 
+use crate::construction::{UnderConstruction, INITIAL_HEALTH};
+
 pub(crate) struct SpawnerPlugin;
 
 impl Plugin for SpawnerPlugin {
@@ -30,34 +32,70 @@ pub struct SpawnBundle {
 }
 
 impl SpawnBundle {
+    /// Creates a bundle of a fully functional object.
     pub fn new(object_type: ObjectType, transform: Transform) -> Self {
+        Self::with_spawn(object_type, transform, Spawn { construct: false })
+    }
+
+    /// Creates a bundle of a building which is spawned under construction,
+    /// see [`crate::UnderConstruction`].
+    pub fn construction(object_type: ObjectType, transform: Transform) -> Self {
+        debug_assert!(matches!(
+            object_type,
+            ObjectType::Active(ActiveObjectType::Building(_))
+        ));
+        Self::with_spawn(object_type, transform, Spawn { construct: true })
+    }
+
+    fn with_spawn(object_type: ObjectType, transform: Transform, spawn: Spawn) -> Self {
         Self {
             object_type,
             transform,
             global_transform: transform.into(),
             visibility: Visibility::visible(),
             computed_visibility: ComputedVisibility::not_visible(),
-            spawn: Spawn,
+            spawn,
         }
     }
 }
 
 #[derive(Component)]
-struct Spawn;
+struct Spawn {
+    construct: bool,
+}
 
 fn spawn(
     mut commands: Commands,
     game_config: Res<GameConfig>,
     cache: Res<ObjectCache>,
     healths: Res<InitialHealths>,
-    to_spawn: Query<(Entity, &ObjectType, Option<&Player>), With<Spawn>>,
+    to_spawn: Query<(Entity, &ObjectType, Option<&Player>, &Spawn)>,
 ) {
-    for (entity, &object_type, player) in to_spawn.iter() {
+    for (entity, &object_type, player, spawn) in to_spawn.iter() {
         info!("Spawning object {}", object_type);
 
         let cache_item = cache.get(object_type);
         let mut entity_commands = commands.entity(entity);
-        entity_commands.remove::<Spawn>().insert(cache_item.scene());
+        entity_commands.remove::<Spawn>();
+
+        match cache_item.turret() {
+            Some(turret) => {
+                // The model is spawned as a child entity so that it can
+                // rotate together with the turret without rotation of the
+                // (static) object itself.
+                entity_commands
+                    .insert(turret.clone())
+                    .with_children(|parent| {
+                        parent.spawn_bundle(SceneBundle {
+                            scene: cache_item.scene(),
+                            ..Default::default()
+                        });
+                    });
+            }
+            None => {
+                entity_commands.insert(cache_item.scene());
+            }
+        }
 
         match object_type {
             ObjectType::Active(active_type) => {
@@ -77,10 +115,16 @@ fn spawn(
                     }
                 }
 
-                entity_commands.insert(healths.health(active_type).clone());
-                if let Some(cannon) = cache_item.cannon() {
-                    entity_commands.insert(cannon.clone());
+                let mut health = healths.health(active_type).clone();
+
+                if spawn.construct {
+                    // Health is gained back during the construction.
+                    health.hit((1. - INITIAL_HEALTH) * health.max(), None);
+                    entity_commands.insert(UnderConstruction::new(cache_item.build_time()));
+                } else {
+                    insert_functions(&mut entity_commands, &cache, object_type);
                 }
+                entity_commands.insert(health);
             }
             ObjectType::Inactive(_) => {
                 entity_commands.insert(StaticSolid);
@@ -88,3 +132,35 @@ fn spawn(
         }
     }
 }
+
+/// Inserts components which make up functionality of an active object, e.g.
+/// its armament, health regeneration or production capability. Buildings get
+/// these only once their construction is finished.
+pub(crate) fn insert_functions(
+    entity_commands: &mut EntityCommands,
+    cache: &ObjectCache,
+    object_type: ObjectType,
+) {
+    let cache_item = cache.get(object_type);
+    if let Some(regeneration) = cache_item.regeneration() {
+        entity_commands.insert(regeneration);
+    }
+    if let Some(armament) = cache_item.armament() {
+        entity_commands.insert(armament.clone());
+    }
+    if let Some(repairer) = cache_item.repairer() {
+        entity_commands.insert(repairer);
+    }
+    if let Some(extractor) = cache_item.extractor() {
+        entity_commands.insert(extractor);
+    }
+    if let Some(source) = cache_item.power_source() {
+        entity_commands.insert(source);
+    }
+    if let Some(consumer) = cache_item.power_consumer() {
+        entity_commands.insert(consumer);
+    }
+    if let Some(factory) = cache_item.factory() {
+        entity_commands.insert(factory.clone());
+    }
+}
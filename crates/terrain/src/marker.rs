@@ -28,6 +28,10 @@ impl Plugin for MarkerPlugin {
 
 /// A semi-transparent circle is drawn on the terrain surface below every
 /// entity with this component.
+///
+/// The entity does not need to be an object (i.e. to have an
+/// [`ObjectType`] component), in which case the circle itself is used for
+/// visibility culling.
 #[derive(Component)]
 pub struct CircleMarker {
     radius: f32,
@@ -49,7 +53,7 @@ fn update_markers(
     camera: Query<(&Transform, &Frustum), With<Camera3d>>,
     terrains: Query<(&ComputedVisibility, &Handle<TerrainMaterial>)>,
     markers: Query<(
-        &ObjectType,
+        Option<&ObjectType>,
         &ComputedVisibility,
         &GlobalTransform,
         &CircleMarker,
@@ -66,15 +70,23 @@ fn update_markers(
     }
 
     let mut candidates = Vec::new();
-    for (&object_type, circle_visibility, transform, marker) in markers.iter() {
+    for (object_type, circle_visibility, transform, marker) in markers.iter() {
         if !circle_visibility.is_visible_in_hierarchy() {
             continue;
         }
 
-        let aabb = cache.get_collider(object_type).aabb();
-        let aabb = Aabb {
-            center: Vec3A::from(aabb.center()),
-            half_extents: Vec3A::from(aabb.half_extents()),
+        let aabb = match object_type {
+            Some(&object_type) => {
+                let aabb = cache.get_collider(object_type).aabb();
+                Aabb {
+                    center: Vec3A::from(aabb.center()),
+                    half_extents: Vec3A::from(aabb.half_extents()),
+                }
+            }
+            None => Aabb {
+                center: Vec3A::ZERO,
+                half_extents: Vec3A::new(marker.radius(), 0., marker.radius()),
+            },
         };
 
         if frustum::intersects_bevy(cam_frustum, transform, &aabb) {
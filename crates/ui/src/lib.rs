@@ -1,13 +1,15 @@
 mod plugin;
+mod stockpile;
 
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
 use plugin::UiPlugin;
 pub use plugin::UpdateSelectionBoxEvent;
+use stockpile::StockpilePlugin;
 
 pub struct UiPluginGroup;
 
 impl PluginGroup for UiPluginGroup {
     fn build(&mut self, group: &mut PluginGroupBuilder) {
-        group.add(UiPlugin);
+        group.add(UiPlugin).add(StockpilePlugin);
     }
 }
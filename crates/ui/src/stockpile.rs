@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use de_core::{gconfig::GameConfig, player::Player, stages::GameStage, state::GameState};
+use de_economy::Stockpiles;
+use de_spawner::Draft;
+use iyes_loopless::prelude::*;
+
+const FONT_PATH: &str = "fonts/DejaVuSans.ttf";
+const FONT_SIZE: f32 = 24.;
+const TEXT_COLOR: Color = Color::WHITE;
+/// Color of the stockpile text while the local player drafts a building
+/// which they cannot afford.
+const INSUFFICIENT_COLOR: Color = Color::rgb(0.9, 0.2, 0.2);
+const MARGIN: f32 = 10.;
+
+pub(crate) struct StockpilePlugin;
+
+impl Plugin for StockpilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::Playing, setup)
+            .add_exit_system(GameState::Playing, cleanup)
+            .add_system_to_stage(
+                GameStage::PostUpdate,
+                update.run_in_state(GameState::Playing),
+            );
+    }
+}
+
+/// Text displaying resources of the local player.
+#[derive(Component)]
+struct StockpileText;
+
+fn setup(mut commands: Commands, server: Res<AssetServer>) {
+    commands
+        .spawn_bundle(
+            TextBundle::from_section(
+                String::new(),
+                TextStyle {
+                    font: server.load(FONT_PATH),
+                    font_size: FONT_SIZE,
+                    color: TEXT_COLOR,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(MARGIN),
+                    right: Val::Px(MARGIN),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        )
+        .insert(StockpileText);
+}
+
+fn cleanup(mut commands: Commands, texts: Query<Entity, With<StockpileText>>) {
+    for entity in texts.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn update(
+    game_config: Res<GameConfig>,
+    stockpiles: Res<Stockpiles>,
+    drafts: Query<(&Player, &Draft)>,
+    mut texts: Query<&mut Text, With<StockpileText>>,
+) {
+    let value = format!("Resources: {}", stockpiles.amount(game_config.player()));
+    let color = if drafts
+        .iter()
+        .any(|(&player, draft)| game_config.is_local_player(player) && !draft.affordable())
+    {
+        INSUFFICIENT_COLOR
+    } else {
+        TEXT_COLOR
+    };
+
+    for mut text in texts.iter_mut() {
+        let section = &text.sections[0];
+        // Access the component mutably only when really needed for optimal
+        // Bevy change detection (text is re-laid out on each change).
+        if section.value != value || section.style.color != color {
+            let section = &mut text.sections[0];
+            section.value = value.clone();
+            section.style.color = color;
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use ahash::AHashMap;
+use bevy::prelude::*;
+use de_core::{
+    objects::{Active, ObjectType},
+    player::Player,
+    projection::ToFlat,
+    stages::GameStage,
+    state::GameState,
+};
+use de_map::size::MapBounds;
+use de_objects::ObjectCache;
+use iyes_loopless::prelude::*;
+
+use crate::{grid::VisionGrid, VisionLabels};
+
+pub(crate) struct FogPlugin;
+
+impl Plugin for FogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::Playing, setup)
+            .add_exit_system(GameState::Playing, destruct)
+            .add_system_to_stage(
+                GameStage::PostMovement,
+                update
+                    .run_in_state(GameState::Playing)
+                    .label(VisionLabels::Update),
+            );
+    }
+}
+
+/// Visibility of the map from the point of view of all players.
+pub struct FogOfWar {
+    bounds: MapBounds,
+    grids: AHashMap<Player, VisionGrid>,
+}
+
+impl FogOfWar {
+    fn new(bounds: MapBounds) -> Self {
+        Self {
+            bounds,
+            grids: AHashMap::new(),
+        }
+    }
+
+    /// Returns true if a point on the map is currently within sight range of
+    /// any active object of a player.
+    pub fn is_visible(&self, player: Player, point: Vec2) -> bool {
+        self.grids
+            .get(&player)
+            .map_or(false, |grid| grid.is_visible(point))
+    }
+
+    /// Returns true if a point on the map has ever been visible to a player.
+    pub fn is_explored(&self, player: Player, point: Vec2) -> bool {
+        self.grids
+            .get(&player)
+            .map_or(false, |grid| grid.is_explored(point))
+    }
+
+    fn clear(&mut self) {
+        for grid in self.grids.values_mut() {
+            grid.clear();
+        }
+    }
+
+    fn reveal(&mut self, player: Player, center: Vec2, radius: f32) {
+        let bounds = &self.bounds;
+        self.grids
+            .entry(player)
+            .or_insert_with(|| VisionGrid::new(bounds))
+            .reveal(center, radius);
+    }
+}
+
+fn setup(mut commands: Commands, bounds: Res<MapBounds>) {
+    commands.insert_resource(FogOfWar::new(*bounds));
+}
+
+fn destruct(mut commands: Commands) {
+    commands.remove_resource::<FogOfWar>();
+}
+
+fn update(
+    mut fog: ResMut<FogOfWar>,
+    cache: Res<ObjectCache>,
+    observers: Query<(&ObjectType, &Player, &Transform), With<Active>>,
+) {
+    fog.clear();
+    for (&object_type, &player, transform) in observers.iter() {
+        if let Some(sight_range) = cache.get(object_type).sight_range() {
+            fog.reveal(player, transform.translation.to_flat(), sight_range);
+        }
+    }
+}
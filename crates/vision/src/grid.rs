@@ -0,0 +1,114 @@
+use de_map::size::MapBounds;
+use glam::{IVec2, Vec2};
+
+use crate::TILE_SIZE;
+
+/// Visibility of the map from the point of view of a single player.
+pub(crate) struct VisionGrid {
+    origin: Vec2,
+    size: IVec2,
+    /// Tiles which are currently within sight range of any of the player's
+    /// objects.
+    visible: Vec<bool>,
+    /// Tiles which have ever been visible.
+    explored: Vec<bool>,
+}
+
+impl VisionGrid {
+    pub(crate) fn new(bounds: &MapBounds) -> Self {
+        let size = (bounds.size() / TILE_SIZE)
+            .ceil()
+            .as_ivec2()
+            .max(IVec2::ONE);
+        let len = (size.x * size.y) as usize;
+        Self {
+            origin: bounds.min(),
+            size,
+            visible: vec![false; len],
+            explored: vec![false; len],
+        }
+    }
+
+    /// Marks all tiles as not visible. Explored tiles stay explored.
+    pub(crate) fn clear(&mut self) {
+        self.visible.fill(false);
+    }
+
+    /// Marks all tiles whose center is within a distance from a point as
+    /// visible and explored.
+    pub(crate) fn reveal(&mut self, center: Vec2, radius: f32) {
+        let min = self.tile(center - radius).max(IVec2::ZERO);
+        let max = self.tile(center + radius).min(self.size - IVec2::ONE);
+        let radius_squared = radius * radius;
+
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let tile = IVec2::new(x, y);
+                let tile_center = self.origin + (tile.as_vec2() + 0.5) * TILE_SIZE;
+                if tile_center.distance_squared(center) <= radius_squared {
+                    let index = self.index(tile);
+                    self.visible[index] = true;
+                    self.explored[index] = true;
+                }
+            }
+        }
+    }
+
+    /// Returns true if a point is currently visible. Points outside of the
+    /// map are never visible.
+    pub(crate) fn is_visible(&self, point: Vec2) -> bool {
+        self.checked_index(point)
+            .map_or(false, |index| self.visible[index])
+    }
+
+    /// Returns true if a point has ever been visible.
+    pub(crate) fn is_explored(&self, point: Vec2) -> bool {
+        self.checked_index(point)
+            .map_or(false, |index| self.explored[index])
+    }
+
+    fn tile(&self, point: Vec2) -> IVec2 {
+        ((point - self.origin) / TILE_SIZE).floor().as_ivec2()
+    }
+
+    fn checked_index(&self, point: Vec2) -> Option<usize> {
+        let tile = self.tile(point);
+        if tile.cmplt(IVec2::ZERO).any() || tile.cmpge(self.size).any() {
+            None
+        } else {
+            Some(self.index(tile))
+        }
+    }
+
+    fn index(&self, tile: IVec2) -> usize {
+        (tile.y * self.size.x + tile.x) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let mut grid = VisionGrid::new(&MapBounds::new(Vec2::splat(100.)));
+        assert!(!grid.is_visible(Vec2::ZERO));
+        assert!(!grid.is_explored(Vec2::ZERO));
+
+        grid.reveal(Vec2::new(10., -20.), 10.);
+        assert!(grid.is_visible(Vec2::new(10., -20.)));
+        assert!(grid.is_visible(Vec2::new(17., -20.)));
+        assert!(!grid.is_visible(Vec2::new(25., -20.)));
+        assert!(!grid.is_visible(Vec2::new(10., 0.)));
+        assert!(!grid.is_visible(Vec2::new(1000., 0.)));
+
+        grid.clear();
+        assert!(!grid.is_visible(Vec2::new(10., -20.)));
+        assert!(grid.is_explored(Vec2::new(10., -20.)));
+        assert!(!grid.is_explored(Vec2::new(-30., 30.)));
+
+        // Partially outside of the map.
+        grid.reveal(Vec2::new(-49., 49.), 8.);
+        assert!(grid.is_visible(Vec2::new(-48., 48.)));
+    }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use de_core::{
+    gconfig::GameConfig, objects::Active, player::Player, projection::ToFlat, stages::GameStage,
+    state::GameState,
+};
+use iyes_loopless::prelude::*;
+
+use crate::{fog::FogOfWar, VisionLabels};
+
+pub(crate) struct HidePlugin;
+
+impl Plugin for HidePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            GameStage::PostMovement,
+            hide.run_in_state(GameState::Playing)
+                .after(VisionLabels::Update),
+        );
+    }
+}
+
+/// Objects of other players which are not visible by the local player are
+/// marked with this component.
+#[derive(Component)]
+pub struct Unseen;
+
+type Meshes<'w, 's> =
+    Query<'w, 's, &'static mut Visibility, (With<Handle<StandardMaterial>>, Without<Active>)>;
+
+fn hide(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    fog: Res<FogOfWar>,
+    mut objects: Query<
+        (
+            Entity,
+            &Player,
+            &Transform,
+            &mut Visibility,
+            Option<&Unseen>,
+        ),
+        With<Active>,
+    >,
+    children: Query<&Children>,
+    mut meshes: Meshes,
+) {
+    let local = config.player();
+
+    for (entity, &player, transform, mut visibility, unseen) in objects.iter_mut() {
+        if player == local {
+            continue;
+        }
+
+        let visible = fog.is_visible(local, transform.translation.to_flat());
+        if visible {
+            if unseen.is_some() {
+                commands.entity(entity).remove::<Unseen>();
+                visibility.is_visible = true;
+                set_meshes_visibility(&children, &mut meshes, entity, true);
+            }
+        } else {
+            if unseen.is_none() {
+                commands.entity(entity).insert(Unseen);
+                visibility.is_visible = false;
+            }
+            // Scenes are spawned asynchronously, thus new meshes might appear
+            // at any time.
+            set_meshes_visibility(&children, &mut meshes, entity, false);
+        }
+    }
+}
+
+/// Sets visibility of meshes of all descendants of an entity. Visibility is
+/// not inherited in the entity hierarchy, thus each descendant mesh has to be
+/// hidden. Health bars and other signs, which use different materials, are
+/// not affected.
+fn set_meshes_visibility(
+    children: &Query<&Children>,
+    meshes: &mut Meshes,
+    entity: Entity,
+    is_visible: bool,
+) {
+    if let Ok(entity_children) = children.get(entity) {
+        for &child in entity_children.iter() {
+            if let Ok(mut visibility) = meshes.get_mut(child) {
+                if visibility.is_visible != is_visible {
+                    visibility.is_visible = is_visible;
+                }
+            }
+            set_meshes_visibility(children, meshes, child, is_visible);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! This crate implements per-player visibility of the game map (fog of war).
+//!
+//! Each player sees areas within sight range of their active objects and
+//! remembers areas which they have ever seen (explored areas). Objects of other
+//! players outside of the local player's vision are hidden and marked with
+//! [`Unseen`].
+
+use bevy::{app::PluginGroupBuilder, prelude::*};
+pub use fog::FogOfWar;
+use fog::FogPlugin;
+use hide::HidePlugin;
+pub use hide::Unseen;
+
+mod fog;
+mod grid;
+mod hide;
+
+/// Size (in world-space) of a single square tile of the visibility grid.
+const TILE_SIZE: f32 = 4.;
+
+pub struct VisionPluginGroup;
+
+impl PluginGroup for VisionPluginGroup {
+    fn build(&mut self, group: &mut PluginGroupBuilder) {
+        group.add(FogPlugin).add(HidePlugin);
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemLabel)]
+pub enum VisionLabels {
+    /// Visibility of the map is updated in systems with this label.
+    Update,
+}
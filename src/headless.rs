@@ -0,0 +1,56 @@
+//! Plugins needed to run the game without a window, rendering and user
+//! input, for example automated matches between computer players.
+
+use bevy::{
+    animation::AnimationPlugin,
+    app::PluginGroupBuilder,
+    asset::AssetPlugin,
+    gltf::GltfPlugin,
+    hierarchy::HierarchyPlugin,
+    log::LogPlugin,
+    prelude::*,
+    render::{mesh::MeshPlugin, primitives::Aabb, texture::ImagePlugin},
+    scene::ScenePlugin,
+    transform::TransformPlugin,
+};
+use de_camera::MoveFocusEvent;
+use de_signs::{UpdateBarValueEvent, UpdateProgressBarEvent};
+
+/// Bevy's [`MinimalPlugins`] extended by plugins needed to load game assets.
+/// No rendering, windowing nor input plugins are included.
+pub(crate) struct HeadlessPlugins;
+
+impl PluginGroup for HeadlessPlugins {
+    fn build(&mut self, group: &mut PluginGroupBuilder) {
+        MinimalPlugins.build(group);
+        group
+            .add(LogPlugin)
+            .add(TransformPlugin)
+            .add(HierarchyPlugin)
+            .add(AssetPlugin)
+            .add(ScenePlugin)
+            .add(ImagePlugin)
+            .add(MeshPlugin)
+            .add(AnimationPlugin)
+            .add(GltfPlugin)
+            .add(HeadlessPlugin);
+    }
+}
+
+/// Registers assets, types and events which are otherwise registered by
+/// rendering plugins or by game plugins concerned solely with graphics and
+/// user interaction. These are still used by the rest of the game.
+struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<StandardMaterial>()
+            // Components of object models, see `bevy::gltf`.
+            .register_type::<Visibility>()
+            .register_type::<ComputedVisibility>()
+            .register_type::<Aabb>()
+            .add_event::<MoveFocusEvent>()
+            .add_event::<UpdateBarValueEvent>()
+            .add_event::<UpdateProgressBarEvent>();
+    }
+}
@@ -1,27 +1,121 @@
+use std::{path::PathBuf, time::Duration};
+
 use bevy::{
+    app::{PluginGroupBuilder, ScheduleRunnerSettings},
     diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
     prelude::*,
     window::WindowMode,
 };
+use clap::Parser;
+use de_ai::{AiPlayers, AiPluginGroup, Difficulty};
 use de_behaviour::BehaviourPluginGroup;
 use de_camera::CameraPluginGroup;
-use de_combat::CombatPluginGroup;
+use de_combat::{CombatPluginGroup, StatsExport};
 use de_controller::ControllerPluginGroup;
 use de_core::{gconfig::GameConfig, player::Player, state::GameState, CorePluginGroup};
+use de_economy::EconomyPluginGroup;
 use de_index::IndexPluginGroup;
 use de_loader::LoaderPluginGroup;
 use de_movement::MovementPluginGroup;
 use de_objects::ObjectsPluginGroup;
 use de_pathing::PathingPluginGroup;
+use de_production::ProductionPluginGroup;
 use de_signs::SignsPluginGroup;
 use de_spawner::SpawnerPluginGroup;
 use de_terrain::TerrainPluginGroup;
 use de_ui::UiPluginGroup;
+use de_vision::VisionPluginGroup;
+use headless::HeadlessPlugins;
 use iyes_loopless::prelude::*;
 
+mod headless;
+
+/// Minimum duration of a single frame of a headless game.
+const HEADLESS_FRAME: Duration = Duration::from_micros(16_667);
+
+#[derive(Parser)]
+#[clap(author, version, about)]
+struct Args {
+    #[clap(
+        long,
+        value_parser,
+        help = "Directory where combat statistics are stored at the end of each game."
+    )]
+    stats_dir: Option<PathBuf>,
+    #[clap(
+        long,
+        value_parser = parse_ai_player,
+        value_name = "PLAYER:DIFFICULTY",
+        help = "Player controlled by the computer, e.g. 2:hard. May be repeated. \
+                Difficulty is one of easy, normal and hard. Player 2 is \
+                controlled by the computer on normal difficulty by default."
+    )]
+    ai: Vec<(Player, Difficulty)>,
+    #[clap(
+        long,
+        action,
+        help = "Run the game without a window, rendering and user input. \
+                Players 1 and 2 are controlled by the computer by default."
+    )]
+    headless: bool,
+}
+
+impl Args {
+    /// Returns all computer controlled players.
+    fn ai_players(&self) -> AiPlayers {
+        if !self.ai.is_empty() {
+            AiPlayers::new(self.ai.iter().copied())
+        } else if self.headless {
+            AiPlayers::new([
+                (Player::Player1, Difficulty::Normal),
+                (Player::Player2, Difficulty::Normal),
+            ])
+        } else {
+            AiPlayers::new([(Player::Player2, Difficulty::Normal)])
+        }
+    }
+}
+
+fn parse_ai_player(value: &str) -> Result<(Player, Difficulty), String> {
+    let (player, difficulty) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected PLAYER:DIFFICULTY, got \"{}\"", value))?;
+
+    let player = match player {
+        "1" => Player::Player1,
+        "2" => Player::Player2,
+        "3" => Player::Player3,
+        "4" => Player::Player4,
+        _ => return Err(format!("invalid player \"{}\", expected 1 to 4", player)),
+    };
+    let difficulty = match difficulty {
+        "easy" => Difficulty::Easy,
+        "normal" => Difficulty::Normal,
+        "hard" => Difficulty::Hard,
+        _ => {
+            return Err(format!(
+                "invalid difficulty \"{}\", expected easy, normal or hard",
+                difficulty
+            ))
+        }
+    };
+
+    Ok((player, difficulty))
+}
+
 fn main() {
-    App::new()
-        .insert_resource(WindowDescriptor {
+    create_app(Args::parse()).run();
+}
+
+fn create_app(args: Args) -> App {
+    let headless = args.headless;
+    let mut app = App::new();
+
+    if headless {
+        app.insert_resource(ScheduleRunnerSettings::run_loop(HEADLESS_FRAME))
+            .add_plugins(HeadlessPlugins);
+    } else {
+        app.insert_resource(WindowDescriptor {
             title: "Digital Extinction".to_string(),
             mode: WindowMode::BorderlessFullscreen,
             ..Default::default()
@@ -29,30 +123,148 @@ fn main() {
         .insert_resource(Msaa { samples: 4 })
         .add_plugins(DefaultPlugins)
         .add_plugin(LogDiagnosticsPlugin::default())
-        .add_plugin(FrameTimeDiagnosticsPlugin::default())
-        .add_plugin(GamePlugin)
-        .add_plugins(CorePluginGroup)
-        .add_plugins(ObjectsPluginGroup)
-        .add_plugins(TerrainPluginGroup)
-        .add_plugins(LoaderPluginGroup)
-        .add_plugins(IndexPluginGroup)
-        .add_plugins(PathingPluginGroup)
-        .add_plugins(SignsPluginGroup)
-        .add_plugins(SpawnerPluginGroup)
-        .add_plugins(MovementPluginGroup)
-        .add_plugins(ControllerPluginGroup)
-        .add_plugins(CameraPluginGroup)
-        .add_plugins(BehaviourPluginGroup)
-        .add_plugins(CombatPluginGroup)
-        .add_plugins(UiPluginGroup)
-        .run();
-}
-
-struct GamePlugin;
+        .add_plugin(FrameTimeDiagnosticsPlugin::default());
+    }
+
+    app.add_plugin(GamePlugin::new(args))
+        .add_plugins(SimulationPluginGroup);
+    if !headless {
+        app.add_plugins(InterfacePluginGroup);
+    }
+
+    app
+}
+
+struct GamePlugin {
+    args: Args,
+}
+
+impl GamePlugin {
+    fn new(args: Args) -> Self {
+        Self { args }
+    }
+}
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.add_loopless_state(GameState::Loading)
-            .insert_resource(GameConfig::new("map.tar", Player::Player1));
+            .insert_resource(GameConfig::new("map.tar", Player::Player1))
+            .insert_resource(self.args.ai_players());
+
+        if let Some(ref stats_dir) = self.args.stats_dir {
+            app.insert_resource(StatsExport::new(stats_dir.clone()));
+        }
+    }
+}
+
+/// Game mechanics, i.e. all plugins which do not depend on rendering or user
+/// input.
+struct SimulationPluginGroup;
+
+impl PluginGroup for SimulationPluginGroup {
+    fn build(&mut self, group: &mut PluginGroupBuilder) {
+        CorePluginGroup.build(group);
+        ObjectsPluginGroup.build(group);
+        LoaderPluginGroup.build(group);
+        IndexPluginGroup.build(group);
+        PathingPluginGroup.build(group);
+        SpawnerPluginGroup.build(group);
+        EconomyPluginGroup.build(group);
+        ProductionPluginGroup.build(group);
+        VisionPluginGroup.build(group);
+        MovementPluginGroup.build(group);
+        BehaviourPluginGroup.build(group);
+        CombatPluginGroup.build(group);
+        AiPluginGroup.build(group);
+    }
+}
+
+/// Graphics and user interaction.
+struct InterfacePluginGroup;
+
+impl PluginGroup for InterfacePluginGroup {
+    fn build(&mut self, group: &mut PluginGroupBuilder) {
+        TerrainPluginGroup.build(group);
+        SignsPluginGroup.build(group);
+        ControllerPluginGroup.build(group);
+        CameraPluginGroup.build(group);
+        UiPluginGroup.build(group);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use de_core::objects::Active;
+
+    use super::*;
+
+    /// Maximum (real) duration of game loading.
+    const LOADING_TIMEOUT: Duration = Duration::from_secs(60);
+    /// Duration of the simulated part of the match.
+    const MATCH_DURATION: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_parse_ai_player() {
+        assert_eq!(
+            parse_ai_player("2:hard"),
+            Ok((Player::Player2, Difficulty::Hard))
+        );
+        assert_eq!(
+            parse_ai_player("4:easy"),
+            Ok((Player::Player4, Difficulty::Easy))
+        );
+        assert!(parse_ai_player("5:easy").is_err());
+        assert!(parse_ai_player("1:impossible").is_err());
+        assert!(parse_ai_player("1").is_err());
+    }
+
+    #[test]
+    fn test_ai_players() {
+        let ai_players = Args::parse_from(["de"]).ai_players();
+        assert_eq!(ai_players.get(Player::Player1), None);
+        assert_eq!(ai_players.get(Player::Player2), Some(Difficulty::Normal));
+
+        let ai_players = Args::parse_from(["de", "--headless"]).ai_players();
+        assert_eq!(ai_players.get(Player::Player1), Some(Difficulty::Normal));
+        assert_eq!(ai_players.get(Player::Player2), Some(Difficulty::Normal));
+
+        let ai_players = Args::parse_from(["de", "--ai", "1:easy", "--ai", "3:hard"]).ai_players();
+        assert_eq!(ai_players.get(Player::Player1), Some(Difficulty::Easy));
+        assert_eq!(ai_players.get(Player::Player2), None);
+        assert_eq!(ai_players.get(Player::Player3), Some(Difficulty::Hard));
+    }
+
+    #[test]
+    fn test_ai_match() {
+        let mut app = create_app(Args::parse_from([
+            "de",
+            "--headless",
+            "--ai",
+            "1:hard",
+            "--ai",
+            "2:hard",
+        ]));
+
+        let start = Instant::now();
+        while app.world.resource::<CurrentState<GameState>>().0 != GameState::Playing {
+            assert!(start.elapsed() < LOADING_TIMEOUT, "Game loading timed out.");
+            app.update();
+        }
+
+        let start = Instant::now();
+        while start.elapsed() < MATCH_DURATION {
+            app.update();
+        }
+
+        assert_eq!(
+            app.world.resource::<CurrentState<GameState>>().0,
+            GameState::Playing
+        );
+        let mut players = app.world.query_filtered::<&Player, With<Active>>();
+        for player in [Player::Player1, Player::Player2] {
+            assert!(players.iter(&app.world).any(|&owner| owner == player));
+        }
     }
 }